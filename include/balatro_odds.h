@@ -0,0 +1,154 @@
+/* Generated from src/ffi/mod.rs by cbindgen, do not edit */
+
+#ifndef BALATRO_ODDS_H
+#define BALATRO_ODDS_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#define BALATRO_ABI_VERSION 1
+
+#define BALATRO_POKER_HAND_COUNT 14
+
+#define BALATRO_SUIT_CLUBS 1
+
+#define BALATRO_SUIT_DIAMONDS 2
+
+#define BALATRO_SUIT_HEARTS 3
+
+#define BALATRO_SUIT_SPADES 4
+
+#define BALATRO_SUIT_WILD 5
+
+#define BALATRO_SEAL_NONE 0
+
+#define BALATRO_SEAL_RED 1
+
+#define BALATRO_SEAL_BLUE 2
+
+#define BALATRO_SEAL_GOLD 3
+
+#define BALATRO_SEAL_PURPLE 4
+
+#define BALATRO_ENHANCEMENT_NONE 0
+
+#define BALATRO_ENHANCEMENT_BONUS 1
+
+#define BALATRO_ENHANCEMENT_MULT 2
+
+#define BALATRO_ENHANCEMENT_STEEL 3
+
+#define BALATRO_ENHANCEMENT_GOLD 4
+
+#define BALATRO_ENHANCEMENT_GLASS 5
+
+#define BALATRO_ENHANCEMENT_LUCKY 6
+
+#define BALATRO_ENHANCEMENT_STONE 7
+
+// A deck handle from one of the `balatro_deck_` constructors, freed with
+// `balatro_deck_free`.
+typedef struct BalatroDeck BalatroDeck;
+
+// A playing card. The rank is 2 to 14 with Ace as 14, the other fields are
+// BALATRO_SUIT_, BALATRO_SEAL_ and BALATRO_ENHANCEMENT_ constants.
+typedef struct BalatroCard {
+  uint8_t rank;
+  uint8_t suit;
+  uint8_t seal;
+  uint8_t enhancement;
+} BalatroCard;
+
+// How often a poker hand could be made, with its 95% confidence interval.
+typedef struct BalatroOdds {
+  uint32_t count;
+  double probability;
+  double ci_low;
+  double ci_high;
+} BalatroOdds;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// BALATRO_ABI_VERSION of the loaded library
+uint32_t balatro_abi_version(void);
+
+// The name of a poker hand index, e.g. "FullHouse", or null if out of range.
+// The string is static and must not be freed.
+const char *balatro_poker_hand_name(int32_t hand);
+
+// A standard 52 card deck
+struct BalatroDeck *balatro_deck_new(void);
+
+// One of the decks in game by name, e.g. "Abandoned", or null if there's no
+// such deck. The seed is only used by the Erratic deck.
+struct BalatroDeck *balatro_deck_from_preset(const char *name, uint64_t seed);
+
+// A deck of the given cards, or null if any of them is invalid
+struct BalatroDeck *balatro_deck_from_cards(const struct BalatroCard *cards, size_t len);
+
+// Frees a deck, null is ignored
+void balatro_deck_free(struct BalatroDeck *deck);
+
+// The number of cards in the deck, 0 for null
+size_t balatro_deck_size(const struct BalatroDeck *deck);
+
+// Copies the card at `index` into `out`, false if it's out of range
+bool balatro_deck_card(const struct BalatroDeck *deck, size_t index, struct BalatroCard *out);
+
+// False if the card is invalid
+bool balatro_deck_add_card(struct BalatroDeck *deck, struct BalatroCard card);
+
+// Removes one copy of the card, false if the card is invalid or not in the
+// deck. A card without a seal or enhancement removes any card of its rank and
+// suit when there is no exact copy.
+bool balatro_deck_remove_card(struct BalatroDeck *deck, struct BalatroCard card);
+
+// The index of the best poker hand in the cards, or -1 if a card is invalid
+int32_t balatro_best_hand(const struct BalatroCard *cards, size_t len);
+
+// Fills `counts`, BALATRO_POKER_HAND_COUNT entries, with 1 for each poker hand
+// the cards contain and 0 for the rest. These are the hands
+// `balatro_best_hand` picks from.
+bool balatro_evaluate_poker_hands(const struct BalatroCard *cards, size_t len, uint32_t *counts);
+
+// Fills `odds`, BALATRO_POKER_HAND_COUNT entries, with how often each poker
+// hand could be made from `hand_size` cards drawn from the deck. The same
+// seed always gives the same counts. False if the deck is empty or smaller
+// than the hand.
+bool balatro_draw_trial(const struct BalatroDeck *deck,
+                        uint8_t hand_size,
+                        uint32_t trials,
+                        uint64_t seed,
+                        struct BalatroOdds *odds);
+
+// Fills `best` with the odds of each poker hand being the best one drawn, and
+// `at_least` with the odds of the best one ranking at least as high. Either
+// can be null, both have BALATRO_POKER_HAND_COUNT entries. False if the deck
+// is empty or smaller than the hand.
+bool balatro_draw_trial_best(const struct BalatroDeck *deck,
+                             uint8_t hand_size,
+                             uint32_t trials,
+                             double *best,
+                             double *at_least);
+
+// Fills `odds`, BALATRO_POKER_HAND_COUNT entries, with the odds of each poker
+// hand when the held cards are kept and `to_draw` more are drawn. The held
+// cards are taken out of the deck first, false if one of them is not in it or
+// fewer than `to_draw` cards are left.
+bool balatro_draw_trial_given(const struct BalatroDeck *deck,
+                              const struct BalatroCard *held,
+                              size_t held_len,
+                              uint8_t to_draw,
+                              uint32_t trials,
+                              double *odds);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* BALATRO_ODDS_H */
@@ -1,10 +1,11 @@
 use core::fmt;
 use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
-use js_sys::Object;
-use wasm_bindgen::JsValue;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum Suit {
     NONE = 0,
     Clubs,
@@ -14,7 +15,10 @@ pub enum Suit {
     Wild,
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[allow(clippy::upper_case_acronyms)]
+// Ranks are numbers on the js side, so serialize them the same way
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(into = "u8", try_from = "u8")]
 pub enum Rank {
     NONE = 0,
     Two = 2,
@@ -33,7 +37,7 @@ pub enum Rank {
 }
 
 impl Suit {
-    pub fn to_str(&self) -> &'static str {
+    pub fn to_str(self) -> &'static str {
         match self {
             Suit::NONE => "NONE",
             Suit::Clubs => "Clubs",
@@ -43,8 +47,11 @@ impl Suit {
             Suit::Wild => "Wild",
         }
     }
+}
 
-    pub fn from_str(s: &str) -> Suit {
+// Anything that isn't a suit is `Suit::NONE`
+impl From<&str> for Suit {
+    fn from(s: &str) -> Suit {
         match s {
             "Clubs" => Suit::Clubs,
             "Diamonds" => Suit::Diamonds,
@@ -54,44 +61,9 @@ impl Suit {
             _ => Suit::NONE,
         }
     }
-
-    pub fn from_jsvalue(jsvalue: JsValue) -> Result<Suit, &'static str> {
-        let suit = match jsvalue.as_string() {
-            Some(suit) => suit,
-            None => return Err("Invalid suit object"),
-        };
-        Ok(Suit::from_str(&suit))
-    }
 }
 
 impl Rank {
-    pub fn from_jsvalue(jsvalue: JsValue) -> Result<Rank, &'static str> {
-        let rank = match jsvalue.as_f64() {
-            Some(rank) => rank as usize,
-            None => return Err("Invalid rank object"),
-        };
-        Ok(Rank::from(rank))
-    }
-
-    pub fn from_str(s: &str) -> Result<Rank, &'static str> {
-        match s {
-            "Two" => Ok(Rank::Two),
-            "Three" => Ok(Rank::Three),
-            "Four" => Ok(Rank::Four),
-            "Five" => Ok(Rank::Five),
-            "Six" => Ok(Rank::Six),
-            "Seven" => Ok(Rank::Seven),
-            "Eight" => Ok(Rank::Eight),
-            "Nine" => Ok(Rank::Nine),
-            "Ten" => Ok(Rank::Ten),
-            "J" => Ok(Rank::Jack),
-            "Q" => Ok(Rank::Queen),
-            "K" => Ok(Rank::King),
-            "A" => Ok(Rank::Ace),
-            _ => Err("Invalid rank"),
-        }
-    }
-
     pub fn next(&self) -> Rank {
         match self {
             Rank::Two => Rank::Three,
@@ -130,7 +102,7 @@ impl Rank {
         }
     }
 
-    pub fn to_str(&self) -> &'static str {
+    pub fn to_str(self) -> &'static str {
         match self {
             Rank::NONE => "NONE",
             Rank::Two => "Two",
@@ -149,7 +121,21 @@ impl Rank {
         }
     }
 
-    pub fn to_int(&self) -> usize {
+    // Chips the rank adds when a card scores
+    pub fn chips(self) -> u32 {
+        match self {
+            Rank::Jack | Rank::Queen | Rank::King => 10,
+            Rank::Ace => 11,
+            Rank::NONE => 0,
+            _ => self.to_int() as u32,
+        }
+    }
+
+    pub fn is_face(self) -> bool {
+        matches!(self, Rank::Jack | Rank::Queen | Rank::King)
+    }
+
+    pub fn to_int(self) -> usize {
         match self {
             Rank::Two => 2,
             Rank::Three => 3,
@@ -207,6 +193,29 @@ impl Rank {
     // }
 }
 
+impl FromStr for Rank {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Rank, &'static str> {
+        match s {
+            "Two" => Ok(Rank::Two),
+            "Three" => Ok(Rank::Three),
+            "Four" => Ok(Rank::Four),
+            "Five" => Ok(Rank::Five),
+            "Six" => Ok(Rank::Six),
+            "Seven" => Ok(Rank::Seven),
+            "Eight" => Ok(Rank::Eight),
+            "Nine" => Ok(Rank::Nine),
+            "Ten" => Ok(Rank::Ten),
+            "J" => Ok(Rank::Jack),
+            "Q" => Ok(Rank::Queen),
+            "K" => Ok(Rank::King),
+            "A" => Ok(Rank::Ace),
+            _ => Err("Invalid rank"),
+        }
+    }
+}
+
 impl Display for Rank {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         let txt = match self.to_str() {
@@ -220,43 +229,164 @@ impl Display for Rank {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Seal {
+    // Retriggers the card
+    Red,
+    // Creates a planet card at the end of the round, no effect on scoring
+    Blue,
+    // $3 when the card scores
+    Gold,
+    // Creates a tarot card when discarded, no effect on scoring
+    Purple,
+}
+
+impl Seal {
+    pub fn to_str(self) -> &'static str {
+        match self {
+            Seal::Red => "Red",
+            Seal::Blue => "Blue",
+            Seal::Gold => "Gold",
+            Seal::Purple => "Purple",
+        }
+    }
+}
+
+impl FromStr for Seal {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Seal, &'static str> {
+        match s {
+            "Red" => Ok(Seal::Red),
+            "Blue" => Ok(Seal::Blue),
+            "Gold" => Ok(Seal::Gold),
+            "Purple" => Ok(Seal::Purple),
+            _ => Err("Invalid seal"),
+        }
+    }
+}
+
+// Wild cards are modelled as `Suit::Wild` instead of an enhancement
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Enhancement {
+    // +30 chips when scored
+    Bonus,
+    // +4 mult when scored
+    Mult,
+    // x1.5 mult while held in hand
+    Steel,
+    // $3 if held in hand at the end of the round
+    Gold,
+    // x2 mult when scored, 1 in 4 chance to be destroyed afterwards
+    Glass,
+    // 1 in 5 chance of +20 mult and 1 in 15 chance of $20 when scored
+    Lucky,
+    // +50 chips, has no rank or suit and always scores
+    Stone,
+}
+
+impl Enhancement {
+    pub fn to_str(self) -> &'static str {
+        match self {
+            Enhancement::Bonus => "Bonus",
+            Enhancement::Mult => "Mult",
+            Enhancement::Steel => "Steel",
+            Enhancement::Gold => "Gold",
+            Enhancement::Glass => "Glass",
+            Enhancement::Lucky => "Lucky",
+            Enhancement::Stone => "Stone",
+        }
+    }
+}
+
+impl FromStr for Enhancement {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Enhancement, &'static str> {
+        match s {
+            "Bonus" => Ok(Enhancement::Bonus),
+            "Mult" => Ok(Enhancement::Mult),
+            "Steel" => Ok(Enhancement::Steel),
+            "Gold" => Ok(Enhancement::Gold),
+            "Glass" => Ok(Enhancement::Glass),
+            "Lucky" => Ok(Enhancement::Lucky),
+            "Stone" => Ok(Enhancement::Stone),
+            _ => Err("Invalid enhancement"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct Card {
     pub rank: Rank,
     pub suit: Suit,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seal: Option<Seal>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enhancement: Option<Enhancement>,
 }
 
 impl Card {
+    pub fn new(rank: Rank, suit: Suit) -> Card {
+        Card {
+            rank,
+            suit,
+            seal: None,
+            enhancement: None,
+        }
+    }
+
+    pub fn with_seal(self, seal: Seal) -> Card {
+        Card {
+            seal: Some(seal),
+            ..self
+        }
+    }
+
+    pub fn with_enhancement(self, enhancement: Enhancement) -> Card {
+        Card {
+            enhancement: Some(enhancement),
+            ..self
+        }
+    }
+
+    // Stone cards keep their rank and suit but don't count as having either
+    pub fn is_stone(&self) -> bool {
+        self.enhancement == Some(Enhancement::Stone)
+    }
+
     pub fn next(&self) -> Card {
         Card {
             rank: self.rank.next(),
-            suit: self.suit,
+            ..*self
         }
     }
     pub fn prev(&self) -> Card {
         Card {
             rank: self.rank.prev(),
-            suit: self.suit,
+            ..*self
         }
     }
 
-    pub fn to_str(&self) -> String {
+    pub fn to_str(self) -> String {
         format!("{} of {}", self.rank.to_str(), self.suit.to_str())
     }
+}
+
+impl From<Rank> for u8 {
+    fn from(rank: Rank) -> Self {
+        rank.to_int() as u8
+    }
+}
+
+impl TryFrom<u8> for Rank {
+    type Error = &'static str;
 
-    pub fn to_jsvalue(&self) -> JsValue {
-        let obj = Object::new();
-        let _ = js_sys::Reflect::set(
-            &obj,
-            &JsValue::from_str("rank"),
-            &JsValue::from_f64(self.rank.to_int() as f64),
-        );
-        let _ = js_sys::Reflect::set(
-            &obj,
-            &JsValue::from_str("suit"),
-            &JsValue::from_str(self.suit.to_str()),
-        );
-        JsValue::from(obj)
+    fn try_from(item: u8) -> Result<Self, Self::Error> {
+        match Rank::from_int(item as usize) {
+            Rank::NONE => Err("Invalid rank"),
+            rank => Ok(rank),
+        }
     }
 }
 
@@ -0,0 +1,253 @@
+use std::collections::HashMap;
+
+use rand::seq::SliceRandom;
+
+use crate::card::{Card, Rank, Suit};
+use crate::hand::Hand;
+use crate::utils::statistics::{generate_random_numbers, mix_seed};
+
+mod ops;
+mod preset;
+pub use ops::DeckOp;
+pub use preset::DeckPreset;
+
+#[cfg(test)]
+mod test;
+
+#[derive(Debug, Clone)]
+pub struct Deck {
+    // Only changed through the methods so every change can be undone
+    cards: Vec<Card>,
+    // Snapshots of the cards before each change, used for undo
+    history: Vec<Vec<Card>>,
+}
+
+impl From<Vec<Card>> for Deck {
+    fn from(cards: Vec<Card>) -> Self {
+        Deck {
+            cards,
+            history: Vec::new(),
+        }
+    }
+}
+
+// A standard 52 card deck
+impl Default for Deck {
+    fn default() -> Self {
+        Deck::new()
+    }
+}
+
+impl Deck {
+    pub fn new() -> Self {
+        let mut cards = Vec::new();
+        for suit in &[Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades] {
+            for rank in 2..=14 {
+                cards.push(Card::new(Rank::from(rank), *suit));
+            }
+        }
+        Deck::from(cards)
+    }
+
+    pub fn from_preset(preset: DeckPreset) -> Self {
+        Deck::from(preset.cards())
+    }
+
+    pub fn reset(&mut self) {
+        self.edit(|cards| *cards = DeckPreset::Standard.cards());
+    }
+
+    // Apply a tarot or spectral card effect, it can be reverted with `undo`
+    pub fn apply(&mut self, op: &DeckOp) -> Result<(), String> {
+        let previous = self.cards.clone();
+        op.apply_to(&mut self.cards)?;
+        self.history.push(previous);
+        Ok(())
+    }
+
+    // Revert the last change to the cards, returns false if there was nothing
+    // to undo
+    pub fn undo(&mut self) -> bool {
+        match self.history.pop() {
+            Some(cards) => {
+                self.cards = cards;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.history.is_empty()
+    }
+
+    // Changes the cards, keeping the old ones for `undo` if anything changed
+    fn edit(&mut self, edit: impl FnOnce(&mut Vec<Card>)) {
+        let previous = self.cards.clone();
+        edit(&mut self.cards);
+        if self.cards != previous {
+            self.history.push(previous);
+        }
+    }
+
+    // Add a card to the deck, allowing duplicates
+    pub fn add_card(&mut self, card: Card) {
+        self.edit(|cards| cards.push(card));
+    }
+
+    // Remove one copy of a card from the deck, returning false if there was
+    // none. A card without a seal or enhancement stands for any card of its
+    // rank and suit when there is no exact copy, since that is often all the
+    // caller knows about it.
+    pub fn remove_card(&mut self, card: &Card) -> bool {
+        let plain = card.seal.is_none() && card.enhancement.is_none();
+        let pos = self.cards.iter().position(|x| x == card).or_else(|| {
+            self.cards
+                .iter()
+                .position(|x| plain && x.rank == card.rank && x.suit == card.suit)
+        });
+        match pos {
+            Some(pos) => {
+                self.edit(|cards| {
+                    cards.remove(pos);
+                });
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn remove_suits(&mut self, suit: &Suit) {
+        self.edit(|cards| cards.retain(|card| card.suit != *suit));
+    }
+
+    pub fn remove_rank(&mut self, rank: &Rank, count: u64) {
+        self.edit(|cards| {
+            let mut i = 0;
+            while i < cards.len() {
+                if cards[i].rank == *rank {
+                    cards.remove(i);
+                } else {
+                    i += 1;
+                    if i as u64 == count {
+                        break;
+                    }
+                }
+            }
+        });
+    }
+
+    pub fn remove_ranks(&mut self, rank: &Rank) {
+        self.edit(|cards| cards.retain(|card| card.rank != *rank));
+    }
+
+    pub fn remove_suit(&mut self, suit: &Suit, count: u64) {
+        self.edit(|cards| {
+            let mut i = 0;
+            while i < cards.len() {
+                if cards[i].suit == *suit {
+                    cards.remove(i);
+                } else {
+                    i += 1;
+                    if i as u64 == count {
+                        break;
+                    }
+                }
+            }
+        });
+    }
+
+    // Errors when the deck can't deal a full hand of `size` cards. The draws
+    // below never fail and deal the whole deck instead, so callers that need
+    // a full hand check first.
+    pub fn check_hand_size(&self, size: u8) -> Result<(), String> {
+        if self.cards.is_empty() {
+            return Err("The deck is empty".to_string());
+        }
+        if size as usize > self.size() {
+            return Err(format!(
+                "Cannot draw {} cards from a deck of {}",
+                size,
+                self.size()
+            ));
+        }
+        Ok(())
+    }
+
+    // Draws `size` random cards, or every card when the deck holds fewer
+    pub fn draw_hand(&self, size: u8) -> Hand {
+        let mut hand = Vec::new();
+        let random_idx = generate_random_numbers(self.size(), size as usize);
+        for i in random_idx {
+            hand.push(self.cards[i]);
+        }
+        Hand::from(hand)
+    }
+
+    // Draws a hand where every card gets a random key from the seed and its
+    // identity, and the cards with the lowest keys are drawn. Two decks that
+    // share most of their cards draw mostly the same hands for the same seed,
+    // which keeps the noise down when comparing them. Like draw_hand, it
+    // deals every card when the deck holds fewer than `size`.
+    pub fn draw_hand_seeded(&self, size: u8, seed: u64) -> Hand {
+        let mut seen: HashMap<Card, u64> = HashMap::new();
+        let mut keyed: Vec<(u64, Card)> = self
+            .cards
+            .iter()
+            .map(|card| {
+                let copy = seen.entry(*card).or_insert(0);
+                *copy += 1;
+                // Modified cards are told apart from plain copies, a Glass
+                // A♠ is drawn independently of a plain one
+                let identity = ((card.rank.to_int() as u64) << 8)
+                    | ((card.suit as u64) << 4)
+                    | card.seal.map_or(0, |seal| seal as u64 + 1)
+                    | (card.enhancement.map_or(0, |e| e as u64 + 1) << 12)
+                    | (*copy << 16);
+                (mix_seed(seed, identity), *card)
+            })
+            .collect();
+        let size = (size as usize).min(keyed.len());
+        if size < keyed.len() {
+            keyed.select_nth_unstable_by_key(size, |(key, _)| *key);
+        }
+        Hand::from(
+            keyed[..size]
+                .iter()
+                .map(|(_, card)| *card)
+                .collect::<Vec<Card>>(),
+        )
+    }
+
+    pub fn count_rank(&self, rank: &Rank) -> u64 {
+        self.cards.iter().filter(|x| &x.rank == rank).count() as u64
+    }
+
+    pub fn count_suit(&self, suit: &Suit) -> u64 {
+        self.cards.iter().filter(|x| &x.suit == suit).count() as u64
+    }
+
+    pub fn count_card(&self, card: &Card) -> u64 {
+        self.cards.iter().filter(|x| *x == card).count() as u64
+    }
+    // Shuffle the deck
+    pub fn shuffle(&mut self) {
+        let mut rng = rand::thread_rng();
+        self.cards.shuffle(&mut rng);
+    }
+    pub fn size(&self) -> usize {
+        self.cards.len()
+    }
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+    pub fn into_cards(self) -> Vec<Card> {
+        self.cards
+    }
+    pub fn sort_by_rank(&mut self) {
+        self.cards.sort_by_key(|card| card.rank);
+    }
+    pub fn sort_by_suit(&mut self) {
+        self.cards.sort_by_key(|card| card.suit);
+    }
+}
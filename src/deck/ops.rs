@@ -0,0 +1,209 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::card::{Card, Rank, Suit};
+
+const SUITS: [Suit; 4] = [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades];
+
+/// A change to the deck made by a tarot or spectral card.
+/// Cards are referred to by their index in `Deck::cards`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "op")]
+pub enum DeckOp {
+    // Increases the rank of up to 2 cards by 1
+    Strength { cards: Vec<usize> },
+    // Turns the target card into a copy of the source card
+    Death { target: usize, source: usize },
+    // Destroys up to 2 cards
+    HangedMan { cards: Vec<usize> },
+    // Converts up to 3 cards to Diamonds
+    Star { cards: Vec<usize> },
+    // Converts up to 3 cards to Clubs
+    Moon { cards: Vec<usize> },
+    // Converts up to 3 cards to Hearts
+    Sun { cards: Vec<usize> },
+    // Converts up to 3 cards to Spades
+    World { cards: Vec<usize> },
+    // Adds 2 copies of a card
+    Cryptid { card: usize },
+    // Destroys a card and adds 3 random face cards
+    Familiar { destroy: usize, seed: u64 },
+    // Destroys a card and adds 2 random Aces
+    Grim { destroy: usize, seed: u64 },
+    // Destroys a card and adds 4 random numbered cards
+    Incantation { destroy: usize, seed: u64 },
+    // Converts all the given cards to a single suit
+    Sigil { cards: Vec<usize>, suit: Suit },
+    // Converts all the given cards to a single rank
+    Ouija { cards: Vec<usize>, rank: Rank },
+}
+
+impl DeckOp {
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            DeckOp::Strength { .. } => "Strength",
+            DeckOp::Death { .. } => "Death",
+            DeckOp::HangedMan { .. } => "The Hanged Man",
+            DeckOp::Star { .. } => "The Star",
+            DeckOp::Moon { .. } => "The Moon",
+            DeckOp::Sun { .. } => "The Sun",
+            DeckOp::World { .. } => "The World",
+            DeckOp::Cryptid { .. } => "Cryptid",
+            DeckOp::Familiar { .. } => "Familiar",
+            DeckOp::Grim { .. } => "Grim",
+            DeckOp::Incantation { .. } => "Incantation",
+            DeckOp::Sigil { .. } => "Sigil",
+            DeckOp::Ouija { .. } => "Ouija",
+        }
+    }
+
+    // The tarot that converts cards to the given suit
+    pub fn suit_change(suit: Suit, cards: Vec<usize>) -> Option<DeckOp> {
+        match suit {
+            Suit::Diamonds => Some(DeckOp::Star { cards }),
+            Suit::Clubs => Some(DeckOp::Moon { cards }),
+            Suit::Hearts => Some(DeckOp::Sun { cards }),
+            Suit::Spades => Some(DeckOp::World { cards }),
+            _ => None,
+        }
+    }
+
+    // Applies the operation to the cards, leaving them untouched on error
+    pub fn apply_to(&self, cards: &mut Vec<Card>) -> Result<(), String> {
+        match self {
+            DeckOp::Strength { cards: selected } => {
+                self.check_selection(cards, selected, 2)?;
+                for &i in selected {
+                    cards[i] = cards[i].next();
+                }
+            }
+            DeckOp::Death { target, source } => {
+                self.check_selection(cards, &[*target, *source], 2)?;
+                cards[*target] = cards[*source];
+            }
+            DeckOp::HangedMan { cards: selected } => {
+                self.check_selection(cards, selected, 2)?;
+                remove_indices(cards, selected);
+            }
+            DeckOp::Star { cards: selected } => {
+                self.convert_suit(cards, selected, Suit::Diamonds)?
+            }
+            DeckOp::Moon { cards: selected } => self.convert_suit(cards, selected, Suit::Clubs)?,
+            DeckOp::Sun { cards: selected } => self.convert_suit(cards, selected, Suit::Hearts)?,
+            DeckOp::World { cards: selected } => {
+                self.convert_suit(cards, selected, Suit::Spades)?
+            }
+            DeckOp::Cryptid { card } => {
+                self.check_selection(cards, &[*card], 1)?;
+                let copy = cards[*card];
+                cards.push(copy);
+                cards.push(copy);
+            }
+            DeckOp::Familiar { destroy, seed } => {
+                self.destroy_and_add(cards, *destroy, *seed, 3, 11..=13)?
+            }
+            DeckOp::Grim { destroy, seed } => {
+                self.destroy_and_add(cards, *destroy, *seed, 2, 14..=14)?
+            }
+            DeckOp::Incantation { destroy, seed } => {
+                self.destroy_and_add(cards, *destroy, *seed, 4, 2..=10)?
+            }
+            DeckOp::Sigil {
+                cards: selected,
+                suit,
+            } => {
+                // In game Wild comes from an enhancement, Sigil only gives the four suits
+                if *suit == Suit::NONE || *suit == Suit::Wild {
+                    return Err("Sigil needs one of the four suits".to_string());
+                }
+                self.check_selection(cards, selected, usize::MAX)?;
+                for &i in selected {
+                    cards[i].suit = *suit;
+                }
+            }
+            DeckOp::Ouija {
+                cards: selected,
+                rank,
+            } => {
+                if *rank == Rank::NONE {
+                    return Err("Ouija needs a rank".to_string());
+                }
+                self.check_selection(cards, selected, usize::MAX)?;
+                for &i in selected {
+                    cards[i].rank = *rank;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn check_selection(
+        &self,
+        cards: &[Card],
+        selected: &[usize],
+        max: usize,
+    ) -> Result<(), String> {
+        if selected.is_empty() {
+            return Err(format!("{} needs at least one card", self.to_str()));
+        }
+        if selected.len() > max {
+            return Err(format!(
+                "{} can select at most {} cards",
+                self.to_str(),
+                max
+            ));
+        }
+        for (n, &i) in selected.iter().enumerate() {
+            if i >= cards.len() {
+                return Err(format!("Card index {} is out of range", i));
+            }
+            if selected[..n].contains(&i) {
+                return Err(format!("Card index {} was selected twice", i));
+            }
+        }
+        Ok(())
+    }
+
+    fn convert_suit(
+        &self,
+        cards: &mut [Card],
+        selected: &[usize],
+        suit: Suit,
+    ) -> Result<(), String> {
+        self.check_selection(cards, selected, 3)?;
+        for &i in selected {
+            cards[i].suit = suit;
+        }
+        Ok(())
+    }
+
+    fn destroy_and_add(
+        &self,
+        cards: &mut Vec<Card>,
+        destroy: usize,
+        seed: u64,
+        count: usize,
+        ranks: std::ops::RangeInclusive<usize>,
+    ) -> Result<(), String> {
+        self.check_selection(cards, &[destroy], 1)?;
+        cards.remove(destroy);
+        let mut rng = StdRng::seed_from_u64(seed);
+        for _ in 0..count {
+            cards.push(Card::new(
+                Rank::from(rng.gen_range(ranks.clone())),
+                SUITS[rng.gen_range(0..SUITS.len())],
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn remove_indices(cards: &mut Vec<Card>, indices: &[usize]) {
+    let mut sorted = indices.to_vec();
+    sorted.sort_unstable();
+    // Remove from the back so the earlier indices stay valid
+    for &i in sorted.iter().rev() {
+        cards.remove(i);
+    }
+}
@@ -0,0 +1,103 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::card::{Card, Rank, Suit};
+
+const SUITS: [Suit; 4] = [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades];
+
+/// The starting decks available in Balatro that change the cards in the deck
+/// or the hand size. Decks that only change economy or jokers play the same as
+/// `Standard` for the purposes of odds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DeckPreset {
+    Standard,
+    // No face cards
+    Abandoned,
+    // 26 Spades and 26 Hearts
+    Checkered,
+    // Every card gets a random rank and suit
+    Erratic(u64),
+    // Standard cards, +2 hand size
+    Painted,
+    // Standard cards, chips and mult are balanced when scoring
+    Plasma,
+}
+
+impl DeckPreset {
+    pub fn to_str(self) -> &'static str {
+        match self {
+            DeckPreset::Standard => "Standard",
+            DeckPreset::Abandoned => "Abandoned",
+            DeckPreset::Checkered => "Checkered",
+            DeckPreset::Erratic(_) => "Erratic",
+            DeckPreset::Painted => "Painted",
+            DeckPreset::Plasma => "Plasma",
+        }
+    }
+
+    // The seed is only used by Erratic, if none is given a random one is picked
+    pub fn from_str(s: &str, seed: Option<u64>) -> Result<DeckPreset, &'static str> {
+        match s.to_lowercase().as_str() {
+            "standard" | "red" | "blue" | "yellow" | "green" | "black" | "magic" | "nebula"
+            | "ghost" | "zodiac" | "anaglyph" => Ok(DeckPreset::Standard),
+            "abandoned" => Ok(DeckPreset::Abandoned),
+            "checkered" => Ok(DeckPreset::Checkered),
+            "erratic" => Ok(DeckPreset::Erratic(
+                seed.unwrap_or_else(|| rand::thread_rng().gen()),
+            )),
+            "painted" => Ok(DeckPreset::Painted),
+            "plasma" => Ok(DeckPreset::Plasma),
+            _ => Err("Invalid deck preset"),
+        }
+    }
+
+    // How much the deck changes the default hand size
+    pub fn hand_size_bonus(self) -> u8 {
+        match self {
+            DeckPreset::Painted => 2,
+            _ => 0,
+        }
+    }
+
+    pub fn cards(self) -> Vec<Card> {
+        match self {
+            DeckPreset::Standard | DeckPreset::Painted | DeckPreset::Plasma => {
+                standard_cards(|_| true)
+            }
+            DeckPreset::Abandoned => {
+                standard_cards(|card| !matches!(card.rank, Rank::Jack | Rank::Queen | Rank::King))
+            }
+            DeckPreset::Checkered => {
+                let mut cards = Vec::with_capacity(52);
+                for suit in [Suit::Spades, Suit::Hearts] {
+                    for rank in (2..=14).chain(2..=14) {
+                        cards.push(Card::new(Rank::from(rank), suit));
+                    }
+                }
+                cards
+            }
+            DeckPreset::Erratic(seed) => {
+                let mut rng = StdRng::seed_from_u64(seed);
+                (0..52)
+                    .map(|_| {
+                        let suit = SUITS[rng.gen_range(0..SUITS.len())];
+                        Card::new(Rank::from(rng.gen_range(2..=14usize)), suit)
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+fn standard_cards(keep: impl Fn(&Card) -> bool) -> Vec<Card> {
+    let mut cards = Vec::with_capacity(52);
+    for suit in SUITS {
+        for rank in 2..=14 {
+            let card = Card::new(Rank::from(rank), suit);
+            if keep(&card) {
+                cards.push(card);
+            }
+        }
+    }
+    cards
+}
@@ -0,0 +1,322 @@
+use crate::{
+    card::{Card, Enhancement, Rank, Seal, Suit},
+    deck::{Deck, DeckOp, DeckPreset},
+};
+
+const RANKS: [Rank; 13] = [
+    Rank::Two,
+    Rank::Three,
+    Rank::Four,
+    Rank::Five,
+    Rank::Six,
+    Rank::Seven,
+    Rank::Eight,
+    Rank::Nine,
+    Rank::Ten,
+    Rank::Jack,
+    Rank::Queen,
+    Rank::King,
+    Rank::Ace,
+];
+const SUITS: [Suit; 4] = [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades];
+
+#[test]
+fn standard_preset_matches_new_deck() {
+    let deck = Deck::from_preset(DeckPreset::Standard);
+    assert_eq!(deck.cards, Deck::new().cards);
+    for rank in RANKS.iter() {
+        assert_eq!(deck.count_rank(rank), 4, "Expected 4 of {}", rank);
+    }
+    for suit in SUITS.iter() {
+        assert_eq!(deck.count_suit(suit), 13, "Expected 13 {}", suit.to_str());
+    }
+}
+
+#[test]
+fn abandoned_preset_has_no_face_cards() {
+    let deck = Deck::from_preset(DeckPreset::Abandoned);
+    assert_eq!(deck.size(), 40);
+    for rank in RANKS.iter() {
+        let expected = match rank {
+            Rank::Jack | Rank::Queen | Rank::King => 0,
+            _ => 4,
+        };
+        assert_eq!(deck.count_rank(rank), expected, "Wrong count of {}", rank);
+    }
+    for suit in SUITS.iter() {
+        assert_eq!(deck.count_suit(suit), 10, "Expected 10 {}", suit.to_str());
+    }
+}
+
+#[test]
+fn checkered_preset_has_only_spades_and_hearts() {
+    let deck = Deck::from_preset(DeckPreset::Checkered);
+    assert_eq!(deck.size(), 52);
+    assert_eq!(deck.count_suit(&Suit::Spades), 26);
+    assert_eq!(deck.count_suit(&Suit::Hearts), 26);
+    assert_eq!(deck.count_suit(&Suit::Clubs), 0);
+    assert_eq!(deck.count_suit(&Suit::Diamonds), 0);
+    for rank in RANKS.iter() {
+        assert_eq!(deck.count_rank(rank), 4, "Expected 4 of {}", rank);
+    }
+}
+
+#[test]
+fn erratic_preset_is_seedable() {
+    let deck = Deck::from_preset(DeckPreset::Erratic(42));
+    assert_eq!(deck.size(), 52);
+    assert_eq!(deck.cards, Deck::from_preset(DeckPreset::Erratic(42)).cards);
+    assert_ne!(deck.cards, Deck::from_preset(DeckPreset::Erratic(43)).cards);
+
+    let rank_total: u64 = RANKS.iter().map(|rank| deck.count_rank(rank)).sum();
+    let suit_total: u64 = SUITS.iter().map(|suit| deck.count_suit(suit)).sum();
+    assert_eq!(rank_total, 52);
+    assert_eq!(suit_total, 52);
+}
+
+#[test]
+fn painted_and_plasma_presets_use_standard_cards() {
+    for preset in [DeckPreset::Painted, DeckPreset::Plasma] {
+        let deck = Deck::from_preset(preset);
+        assert_eq!(deck.cards, Deck::new().cards);
+    }
+    assert_eq!(DeckPreset::Painted.hand_size_bonus(), 2);
+    assert_eq!(DeckPreset::Plasma.hand_size_bonus(), 0);
+}
+
+#[test]
+fn presets_parse_from_names() {
+    assert_eq!(
+        DeckPreset::from_str("Checkered", None),
+        Ok(DeckPreset::Checkered)
+    );
+    assert_eq!(DeckPreset::from_str("red", None), Ok(DeckPreset::Standard));
+    assert_eq!(
+        DeckPreset::from_str("erratic", Some(7)),
+        Ok(DeckPreset::Erratic(7))
+    );
+    assert!(DeckPreset::from_str("not a deck", None).is_err());
+}
+
+fn card(rank: Rank, suit: Suit) -> Card {
+    Card::new(rank, suit)
+}
+
+#[test]
+fn strength_raises_rank_and_can_be_undone() {
+    let mut deck = Deck::from(vec![
+        card(Rank::Two, Suit::Clubs),
+        card(Rank::Ace, Suit::Hearts),
+        card(Rank::King, Suit::Spades),
+    ]);
+    deck.apply(&DeckOp::Strength { cards: vec![0, 2] }).unwrap();
+    assert_eq!(deck.cards[0], card(Rank::Three, Suit::Clubs));
+    assert_eq!(deck.cards[1], card(Rank::Ace, Suit::Hearts));
+    assert_eq!(deck.cards[2], card(Rank::Ace, Suit::Spades));
+
+    assert!(deck.undo());
+    assert_eq!(deck.count_rank(&Rank::Two), 1);
+    assert_eq!(deck.count_rank(&Rank::King), 1);
+    assert!(!deck.undo());
+}
+
+#[test]
+fn added_and_removed_cards_are_undone_in_order() {
+    let mut deck = Deck::new();
+    deck.apply(&DeckOp::Strength { cards: vec![0] }).unwrap();
+    let ace = card(Rank::Ace, Suit::Hearts);
+    deck.add_card(ace);
+    deck.remove_card(&card(Rank::King, Suit::Spades));
+    assert_eq!(deck.size(), 52);
+
+    // Each undo reverts one change, the last one first
+    assert!(deck.undo());
+    assert_eq!(deck.count_card(&card(Rank::King, Suit::Spades)), 1);
+    assert_eq!(deck.count_card(&ace), 2);
+    assert!(deck.undo());
+    assert_eq!(deck.count_card(&ace), 1);
+    assert_eq!(deck.count_rank(&Rank::Two), 3);
+    assert!(deck.undo());
+    assert_eq!(deck.cards, Deck::new().cards);
+
+    // Removing a card that isn't there changes nothing to undo
+    assert!(!deck.remove_card(&card(Rank::Ace, Suit::Wild)));
+    assert!(!deck.can_undo());
+}
+
+#[test]
+fn plain_cards_remove_modified_cards_of_the_same_rank_and_suit() {
+    let glass = card(Rank::Ace, Suit::Spades).with_enhancement(Enhancement::Glass);
+    let mut deck = Deck::from(vec![glass, card(Rank::King, Suit::Spades)]);
+    // A modified card only removes an exact copy
+    assert!(!deck.remove_card(&glass.with_seal(Seal::Red)));
+    assert!(deck.remove_card(&card(Rank::Ace, Suit::Spades)));
+    assert_eq!(deck.cards, vec![card(Rank::King, Suit::Spades)]);
+}
+
+#[test]
+fn modified_duplicates_are_drawn_independently() {
+    let ace = card(Rank::Ace, Suit::Spades);
+    let glass = ace.with_enhancement(Enhancement::Glass);
+    let mut deck = Deck::new();
+    deck.add_card(glass);
+    let (mut either, mut both) = (0, 0);
+    for seed in 0..500 {
+        let hand = deck.draw_hand_seeded(8, seed);
+        let plain = hand.cards.contains(&ace);
+        let modified = hand.cards.contains(&glass);
+        either += (plain || modified) as u32;
+        both += (plain && modified) as u32;
+    }
+    // Drawn together every time if they shared a key
+    assert!(either > 0);
+    assert!(both < either / 2);
+}
+
+#[test]
+fn draws_deal_the_whole_deck_when_it_is_too_small() {
+    assert!(Deck::from(Vec::new()).draw_hand(5).cards.is_empty());
+    let pair = Deck::from(vec![card(Rank::Ace, Suit::Spades); 2]);
+    assert_eq!(pair.draw_hand(5).cards.len(), 2);
+    assert!(pair.check_hand_size(2).is_ok());
+    assert!(pair.check_hand_size(3).is_err());
+    assert!(Deck::from(Vec::new()).check_hand_size(0).is_err());
+}
+
+#[test]
+fn draws_reach_every_card_of_large_decks() {
+    // Only the cards past the first 256 are Aces
+    let mut cards = vec![card(Rank::Two, Suit::Clubs); 256];
+    cards.extend(vec![card(Rank::Ace, Suit::Spades); 44]);
+    let deck = Deck::from(cards);
+    let aces = (0..20)
+        .flat_map(|_| deck.draw_hand(100).cards)
+        .filter(|card| card.rank == Rank::Ace)
+        .count();
+    assert!(aces > 0);
+}
+
+#[test]
+fn selection_limits_are_enforced() {
+    let mut deck = Deck::new();
+    let too_many = DeckOp::HangedMan {
+        cards: vec![0, 1, 2],
+    };
+    assert!(deck.apply(&too_many).is_err());
+    assert!(deck.apply(&DeckOp::Star { cards: vec![60] }).is_err());
+    assert!(deck.apply(&DeckOp::Moon { cards: vec![1, 1] }).is_err());
+    assert!(deck.apply(&DeckOp::Sun { cards: vec![] }).is_err());
+    // Failed ops don't change the deck or the undo stack
+    assert_eq!(deck.cards, Deck::new().cards);
+    assert!(!deck.can_undo());
+}
+
+#[test]
+fn destroy_copy_and_suit_ops_update_counts() {
+    let mut deck = Deck::new();
+    // Two of Clubs and Three of Clubs
+    deck.apply(&DeckOp::HangedMan { cards: vec![1, 0] })
+        .unwrap();
+    assert_eq!(deck.size(), 50);
+    assert_eq!(deck.count_suit(&Suit::Clubs), 11);
+
+    let king_of_spades = card(Rank::King, Suit::Spades);
+    let idx = deck
+        .cards
+        .iter()
+        .position(|c| *c == king_of_spades)
+        .unwrap();
+    deck.apply(&DeckOp::Cryptid { card: idx }).unwrap();
+    assert_eq!(deck.count_card(&king_of_spades), 3);
+
+    deck.apply(&DeckOp::Death {
+        target: 0,
+        source: idx,
+    })
+    .unwrap();
+    assert_eq!(deck.count_card(&king_of_spades), 4);
+    assert_eq!(deck.count_rank(&Rank::King), 7);
+
+    deck.apply(&DeckOp::World {
+        cards: vec![1, 2, 3],
+    })
+    .unwrap();
+    assert_eq!(deck.count_suit(&Suit::Spades), 13 + 2 + 1 + 3);
+
+    while deck.undo() {}
+    assert_eq!(deck.cards, Deck::new().cards);
+}
+
+#[test]
+fn spectral_ops_add_the_right_cards() {
+    let mut deck = Deck::new();
+    deck.apply(&DeckOp::Familiar {
+        destroy: 0,
+        seed: 1,
+    })
+    .unwrap();
+    assert_eq!(deck.size(), 54);
+    let faces: u64 = [Rank::Jack, Rank::Queen, Rank::King]
+        .iter()
+        .map(|rank| deck.count_rank(rank))
+        .sum();
+    assert_eq!(faces, 12 + 3);
+
+    deck.apply(&DeckOp::Grim {
+        destroy: 0,
+        seed: 1,
+    })
+    .unwrap();
+    assert_eq!(deck.size(), 55);
+    assert_eq!(deck.count_rank(&Rank::Ace), 6);
+
+    deck.apply(&DeckOp::Incantation {
+        destroy: 0,
+        seed: 1,
+    })
+    .unwrap();
+    assert_eq!(deck.size(), 58);
+
+    let mut same_seed = Deck::new();
+    same_seed
+        .apply(&DeckOp::Familiar {
+            destroy: 0,
+            seed: 1,
+        })
+        .unwrap();
+    assert_eq!(same_seed.cards[51..], deck.cards[49..52]);
+}
+
+#[test]
+fn sigil_and_ouija_make_cards_uniform() {
+    let mut deck = Deck::new();
+    let hand: Vec<usize> = vec![0, 13, 26, 39, 5];
+    deck.apply(&DeckOp::Sigil {
+        cards: hand.clone(),
+        suit: Suit::Hearts,
+    })
+    .unwrap();
+    assert!(hand.iter().all(|&i| deck.cards[i].suit == Suit::Hearts));
+    assert_eq!(deck.count_suit(&Suit::Hearts), 13 + 4);
+
+    deck.apply(&DeckOp::Ouija {
+        cards: hand.clone(),
+        rank: Rank::Seven,
+    })
+    .unwrap();
+    assert!(hand.iter().all(|&i| deck.cards[i].rank == Rank::Seven));
+    assert_eq!(deck.count_rank(&Rank::Seven), 4 + 5 - 1);
+}
+
+#[test]
+fn sigil_only_converts_to_the_four_suits() {
+    let mut deck = Deck::new();
+    for suit in [Suit::NONE, Suit::Wild] {
+        let op = DeckOp::Sigil {
+            cards: vec![0, 1],
+            suit,
+        };
+        assert!(deck.apply(&op).is_err());
+    }
+    assert_eq!(deck.cards, Deck::new().cards);
+}
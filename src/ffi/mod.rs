@@ -0,0 +1,442 @@
+// C ABI for mods and other native callers, cbindgen generates the header
+// include/balatro_odds.h from this file. Cards are plain structs of the
+// BALATRO_ constants, decks are opaque handles and poker hands are indexes
+// into PokerHand::ALL.
+// Every pointer argument must be null or valid for the stated length, null
+// pointers make the call fail instead of crashing, and so do draws larger
+// than the deck. A panic never unwinds into C, the call fails instead.
+// Most of the constants are only read from C, through the header.
+#![allow(dead_code, clippy::missing_safety_doc)]
+
+use std::ffi::{c_char, CStr};
+use std::panic::{self, AssertUnwindSafe};
+use std::slice;
+
+use crate::card::{Card, Enhancement, Rank, Seal, Suit};
+use crate::deck::{Deck, DeckPreset};
+use crate::hand::{Hand, PokerHand};
+use crate::simulation::results::draw_trial_results;
+use crate::simulation::{self, Threads};
+
+#[cfg(test)]
+mod test;
+
+// Bumped whenever a function or struct changes, callers can check it at load
+pub const BALATRO_ABI_VERSION: u32 = 1;
+
+pub const BALATRO_POKER_HAND_COUNT: usize = 14;
+
+pub const BALATRO_SUIT_CLUBS: u8 = 1;
+pub const BALATRO_SUIT_DIAMONDS: u8 = 2;
+pub const BALATRO_SUIT_HEARTS: u8 = 3;
+pub const BALATRO_SUIT_SPADES: u8 = 4;
+pub const BALATRO_SUIT_WILD: u8 = 5;
+
+pub const BALATRO_SEAL_NONE: u8 = 0;
+pub const BALATRO_SEAL_RED: u8 = 1;
+pub const BALATRO_SEAL_BLUE: u8 = 2;
+pub const BALATRO_SEAL_GOLD: u8 = 3;
+pub const BALATRO_SEAL_PURPLE: u8 = 4;
+
+pub const BALATRO_ENHANCEMENT_NONE: u8 = 0;
+pub const BALATRO_ENHANCEMENT_BONUS: u8 = 1;
+pub const BALATRO_ENHANCEMENT_MULT: u8 = 2;
+pub const BALATRO_ENHANCEMENT_STEEL: u8 = 3;
+pub const BALATRO_ENHANCEMENT_GOLD: u8 = 4;
+pub const BALATRO_ENHANCEMENT_GLASS: u8 = 5;
+pub const BALATRO_ENHANCEMENT_LUCKY: u8 = 6;
+pub const BALATRO_ENHANCEMENT_STONE: u8 = 7;
+
+/// A playing card. The rank is 2 to 14 with Ace as 14, the other fields are
+/// BALATRO_SUIT_, BALATRO_SEAL_ and BALATRO_ENHANCEMENT_ constants.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BalatroCard {
+    pub rank: u8,
+    pub suit: u8,
+    pub seal: u8,
+    pub enhancement: u8,
+}
+
+/// A deck handle from one of the `balatro_deck_` constructors, freed with
+/// `balatro_deck_free`.
+pub struct BalatroDeck {
+    deck: Deck,
+}
+
+/// How often a poker hand could be made, with its 95% confidence interval.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BalatroOdds {
+    pub count: u32,
+    pub probability: f64,
+    pub ci_low: f64,
+    pub ci_high: f64,
+}
+
+const SUITS: [Suit; 5] = [
+    Suit::Clubs,
+    Suit::Diamonds,
+    Suit::Hearts,
+    Suit::Spades,
+    Suit::Wild,
+];
+const SEALS: [Seal; 4] = [Seal::Red, Seal::Blue, Seal::Gold, Seal::Purple];
+const ENHANCEMENTS: [Enhancement; 7] = [
+    Enhancement::Bonus,
+    Enhancement::Mult,
+    Enhancement::Steel,
+    Enhancement::Gold,
+    Enhancement::Glass,
+    Enhancement::Lucky,
+    Enhancement::Stone,
+];
+
+// Same names as the serialized poker hands, in the order of PokerHand::ALL
+const POKER_HAND_NAMES: [&CStr; BALATRO_POKER_HAND_COUNT] = [
+    c"None",
+    c"HighCard",
+    c"Pair",
+    c"TwoPair",
+    c"ThreeOfAKind",
+    c"Straight",
+    c"Flush",
+    c"FullHouse",
+    c"FourOfAKind",
+    c"StraightFlush",
+    c"RoyalFlush",
+    c"FiveOfAKind",
+    c"FlushHouse",
+    c"FlushFive",
+];
+
+// The constants are 1 based so 0 can mean none
+fn from_code<T: Copy>(values: &[T], code: u8) -> Option<T> {
+    values.get((code as usize).checked_sub(1)?).copied()
+}
+
+fn to_code<T: PartialEq>(values: &[T], value: &T) -> u8 {
+    values
+        .iter()
+        .position(|v| v == value)
+        .map_or(0, |i| i as u8 + 1)
+}
+
+fn to_card(card: &BalatroCard) -> Option<Card> {
+    let rank = match card.rank {
+        2..=14 => Rank::from_int(card.rank as usize),
+        _ => return None,
+    };
+    let seal = match card.seal {
+        BALATRO_SEAL_NONE => None,
+        code => Some(from_code(&SEALS, code)?),
+    };
+    let enhancement = match card.enhancement {
+        BALATRO_ENHANCEMENT_NONE => None,
+        code => Some(from_code(&ENHANCEMENTS, code)?),
+    };
+    Some(Card {
+        rank,
+        suit: from_code(&SUITS, card.suit)?,
+        seal,
+        enhancement,
+    })
+}
+
+fn from_card(card: &Card) -> BalatroCard {
+    BalatroCard {
+        rank: card.rank.to_int() as u8,
+        suit: to_code(&SUITS, &card.suit),
+        seal: card
+            .seal
+            .map_or(BALATRO_SEAL_NONE, |seal| to_code(&SEALS, &seal)),
+        enhancement: card
+            .enhancement
+            .map_or(BALATRO_ENHANCEMENT_NONE, |enhancement| {
+                to_code(&ENHANCEMENTS, &enhancement)
+            }),
+    }
+}
+
+// None if the pointer is null or any of the cards is invalid
+unsafe fn cards_from_raw(cards: *const BalatroCard, len: usize) -> Option<Vec<Card>> {
+    if len == 0 {
+        return Some(Vec::new());
+    }
+    if cards.is_null() {
+        return None;
+    }
+    slice::from_raw_parts(cards, len)
+        .iter()
+        .map(to_card)
+        .collect()
+}
+
+unsafe fn out_slice<'a, T>(out: *mut T) -> Option<&'a mut [T]> {
+    if out.is_null() {
+        return None;
+    }
+    Some(slice::from_raw_parts_mut(out, BALATRO_POKER_HAND_COUNT))
+}
+
+fn new_deck(deck: Deck) -> *mut BalatroDeck {
+    Box::into_raw(Box::new(BalatroDeck { deck }))
+}
+
+// Unwinding into C is undefined behaviour, so every entry point runs through
+// this and returns `failed` if it panics
+fn guard<T>(failed: T, call: impl FnOnce() -> T) -> T {
+    panic::catch_unwind(AssertUnwindSafe(call)).unwrap_or(failed)
+}
+
+/// BALATRO_ABI_VERSION of the loaded library
+#[no_mangle]
+pub extern "C" fn balatro_abi_version() -> u32 {
+    guard(0, || BALATRO_ABI_VERSION)
+}
+
+/// The name of a poker hand index, e.g. "FullHouse", or null if out of range.
+/// The string is static and must not be freed.
+#[no_mangle]
+pub extern "C" fn balatro_poker_hand_name(hand: i32) -> *const c_char {
+    guard(std::ptr::null(), || {
+        usize::try_from(hand)
+            .ok()
+            .and_then(|hand| POKER_HAND_NAMES.get(hand))
+            .map_or(std::ptr::null(), |name| name.as_ptr())
+    })
+}
+
+/// A standard 52 card deck
+#[no_mangle]
+pub extern "C" fn balatro_deck_new() -> *mut BalatroDeck {
+    guard(std::ptr::null_mut(), || new_deck(Deck::new()))
+}
+
+/// One of the decks in game by name, e.g. "Abandoned", or null if there's no
+/// such deck. The seed is only used by the Erratic deck.
+#[no_mangle]
+pub unsafe extern "C" fn balatro_deck_from_preset(
+    name: *const c_char,
+    seed: u64,
+) -> *mut BalatroDeck {
+    guard(std::ptr::null_mut(), || {
+        if name.is_null() {
+            return std::ptr::null_mut();
+        }
+        let preset = CStr::from_ptr(name)
+            .to_str()
+            .ok()
+            .and_then(|name| DeckPreset::from_str(name, Some(seed)).ok());
+        match preset {
+            Some(preset) => new_deck(Deck::from_preset(preset)),
+            None => std::ptr::null_mut(),
+        }
+    })
+}
+
+/// A deck of the given cards, or null if any of them is invalid
+#[no_mangle]
+pub unsafe extern "C" fn balatro_deck_from_cards(
+    cards: *const BalatroCard,
+    len: usize,
+) -> *mut BalatroDeck {
+    guard(std::ptr::null_mut(), || match cards_from_raw(cards, len) {
+        Some(cards) => new_deck(Deck::from(cards)),
+        None => std::ptr::null_mut(),
+    })
+}
+
+/// Frees a deck, null is ignored
+#[no_mangle]
+pub unsafe extern "C" fn balatro_deck_free(deck: *mut BalatroDeck) {
+    guard((), || {
+        if !deck.is_null() {
+            drop(Box::from_raw(deck));
+        }
+    })
+}
+
+/// The number of cards in the deck, 0 for null
+#[no_mangle]
+pub unsafe extern "C" fn balatro_deck_size(deck: *const BalatroDeck) -> usize {
+    guard(0, || deck.as_ref().map_or(0, |deck| deck.deck.size()))
+}
+
+/// Copies the card at `index` into `out`, false if it's out of range
+#[no_mangle]
+pub unsafe extern "C" fn balatro_deck_card(
+    deck: *const BalatroDeck,
+    index: usize,
+    out: *mut BalatroCard,
+) -> bool {
+    guard(false, || match (deck.as_ref(), out.as_mut()) {
+        (Some(deck), Some(out)) => match deck.deck.cards().get(index) {
+            Some(card) => {
+                *out = from_card(card);
+                true
+            }
+            None => false,
+        },
+        _ => false,
+    })
+}
+
+/// False if the card is invalid
+#[no_mangle]
+pub unsafe extern "C" fn balatro_deck_add_card(deck: *mut BalatroDeck, card: BalatroCard) -> bool {
+    guard(false, || match (deck.as_mut(), to_card(&card)) {
+        (Some(deck), Some(card)) => {
+            deck.deck.add_card(card);
+            true
+        }
+        _ => false,
+    })
+}
+
+/// Removes one copy of the card, false if the card is invalid or not in the
+/// deck. A card without a seal or enhancement removes any card of its rank and
+/// suit when there is no exact copy.
+#[no_mangle]
+pub unsafe extern "C" fn balatro_deck_remove_card(
+    deck: *mut BalatroDeck,
+    card: BalatroCard,
+) -> bool {
+    guard(false, || match (deck.as_mut(), to_card(&card)) {
+        (Some(deck), Some(card)) => deck.deck.remove_card(&card),
+        _ => false,
+    })
+}
+
+/// The index of the best poker hand in the cards, or -1 if a card is invalid
+#[no_mangle]
+pub unsafe extern "C" fn balatro_best_hand(cards: *const BalatroCard, len: usize) -> i32 {
+    guard(-1, || {
+        match cards_from_raw(cards, len) {
+            // The variants are in the order of PokerHand::ALL
+            Some(cards) => Hand::from(cards).best_hand() as i32,
+            None => -1,
+        }
+    })
+}
+
+/// Fills `counts`, BALATRO_POKER_HAND_COUNT entries, with 1 for each poker hand
+/// the cards contain and 0 for the rest. These are the hands
+/// `balatro_best_hand` picks from.
+#[no_mangle]
+pub unsafe extern "C" fn balatro_evaluate_poker_hands(
+    cards: *const BalatroCard,
+    len: usize,
+    counts: *mut u32,
+) -> bool {
+    guard(false, || {
+        let (Some(cards), Some(counts)) = (cards_from_raw(cards, len), out_slice(counts)) else {
+            return false;
+        };
+        let evaluated = Hand::from(cards).contained_hands();
+        for (hand, count) in PokerHand::ALL.iter().zip(counts.iter_mut()) {
+            *count = evaluated.get(hand).copied().unwrap_or(0);
+        }
+        true
+    })
+}
+
+/// Fills `odds`, BALATRO_POKER_HAND_COUNT entries, with how often each poker
+/// hand could be made from `hand_size` cards drawn from the deck. The same
+/// seed always gives the same counts. False if the deck is empty or smaller
+/// than the hand.
+#[no_mangle]
+pub unsafe extern "C" fn balatro_draw_trial(
+    deck: *const BalatroDeck,
+    hand_size: u8,
+    trials: u32,
+    seed: u64,
+    odds: *mut BalatroOdds,
+) -> bool {
+    guard(false, || {
+        let (Some(deck), Some(odds)) = (deck.as_ref(), out_slice(odds)) else {
+            return false;
+        };
+        if deck.deck.check_hand_size(hand_size).is_err() {
+            return false;
+        }
+        let results = draw_trial_results(&deck.deck, hand_size, trials, seed, Threads::Pool);
+        for (result, odds) in results.results.iter().zip(odds.iter_mut()) {
+            *odds = BalatroOdds {
+                count: result.count,
+                probability: result.probability,
+                ci_low: result.ci.low,
+                ci_high: result.ci.high,
+            };
+        }
+        true
+    })
+}
+
+/// Fills `best` with the odds of each poker hand being the best one drawn, and
+/// `at_least` with the odds of the best one ranking at least as high. Either
+/// can be null, both have BALATRO_POKER_HAND_COUNT entries. False if the deck
+/// is empty or smaller than the hand.
+#[no_mangle]
+pub unsafe extern "C" fn balatro_draw_trial_best(
+    deck: *const BalatroDeck,
+    hand_size: u8,
+    trials: u32,
+    best: *mut f64,
+    at_least: *mut f64,
+) -> bool {
+    guard(false, || {
+        let Some(deck) = deck.as_ref() else {
+            return false;
+        };
+        let Ok(odds) = simulation::best_hand_trials(&deck.deck, hand_size, trials, Threads::Pool)
+        else {
+            return false;
+        };
+        for (out, odds) in [(best, &odds.best), (at_least, &odds.at_least)] {
+            if let Some(out) = out_slice(out) {
+                for (hand, out) in PokerHand::ALL.iter().zip(out.iter_mut()) {
+                    *out = odds.get(hand).copied().unwrap_or(0.0);
+                }
+            }
+        }
+        true
+    })
+}
+
+/// Fills `odds`, BALATRO_POKER_HAND_COUNT entries, with the odds of each poker
+/// hand when the held cards are kept and `to_draw` more are drawn. The held
+/// cards are taken out of the deck first, false if one of them is not in it or
+/// fewer than `to_draw` cards are left.
+#[no_mangle]
+pub unsafe extern "C" fn balatro_draw_trial_given(
+    deck: *const BalatroDeck,
+    held: *const BalatroCard,
+    held_len: usize,
+    to_draw: u8,
+    trials: u32,
+    odds: *mut f64,
+) -> bool {
+    guard(false, || {
+        let (Some(deck), Some(held), Some(odds)) = (
+            deck.as_ref(),
+            cards_from_raw(held, held_len),
+            out_slice(odds),
+        ) else {
+            return false;
+        };
+        let mut deck = deck.deck.clone();
+        if !held.iter().all(|card| deck.remove_card(card)) {
+            return false;
+        }
+        let Ok(given) =
+            simulation::draw_trials_given(&Hand::from(held), &deck, to_draw, trials, Threads::Pool)
+        else {
+            return false;
+        };
+        for (hand, odds) in PokerHand::ALL.iter().zip(odds.iter_mut()) {
+            *odds = given.get(hand).copied().unwrap_or(0.0);
+        }
+        true
+    })
+}
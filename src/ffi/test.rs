@@ -0,0 +1,107 @@
+use std::ffi::CStr;
+use std::ptr;
+
+use super::*;
+
+fn card(rank: u8, suit: u8) -> BalatroCard {
+    BalatroCard {
+        rank,
+        suit,
+        seal: BALATRO_SEAL_NONE,
+        enhancement: BALATRO_ENHANCEMENT_NONE,
+    }
+}
+
+#[test]
+fn cards_round_trip_through_the_codes() {
+    let glass = BalatroCard {
+        seal: BALATRO_SEAL_PURPLE,
+        enhancement: BALATRO_ENHANCEMENT_GLASS,
+        ..card(14, BALATRO_SUIT_WILD)
+    };
+    let converted = to_card(&glass).unwrap();
+    assert_eq!(converted.rank, Rank::Ace);
+    assert_eq!(converted.suit, Suit::Wild);
+    assert_eq!(converted.seal, Some(Seal::Purple));
+    assert_eq!(converted.enhancement, Some(Enhancement::Glass));
+    assert_eq!(from_card(&converted), glass);
+
+    assert_eq!(to_card(&card(1, BALATRO_SUIT_CLUBS)), None);
+    assert_eq!(to_card(&card(2, 0)), None);
+    assert_eq!(to_card(&card(2, 6)), None);
+}
+
+#[test]
+fn hand_names_match_the_serialized_names() {
+    assert_eq!(BALATRO_POKER_HAND_COUNT, PokerHand::ALL.len());
+    for (i, hand) in PokerHand::ALL.into_iter().enumerate() {
+        assert_eq!(i, hand as usize);
+        let name = unsafe { CStr::from_ptr(balatro_poker_hand_name(i as i32)) };
+        assert_eq!(name.to_str().unwrap(), hand.to_str());
+    }
+}
+
+#[test]
+fn evaluates_hands_by_index() {
+    let cards = [
+        card(13, BALATRO_SUIT_SPADES),
+        card(13, BALATRO_SUIT_HEARTS),
+        card(13, BALATRO_SUIT_CLUBS),
+        card(9, BALATRO_SUIT_SPADES),
+        card(9, BALATRO_SUIT_HEARTS),
+    ];
+    let best = unsafe { balatro_best_hand(cards.as_ptr(), cards.len()) };
+    assert_eq!(best, PokerHand::FullHouse as i32);
+    let name = unsafe { CStr::from_ptr(balatro_poker_hand_name(best)) };
+    assert_eq!(name.to_str().unwrap(), PokerHand::FullHouse.to_str());
+    assert!(balatro_poker_hand_name(BALATRO_POKER_HAND_COUNT as i32).is_null());
+
+    let mut counts = [0; BALATRO_POKER_HAND_COUNT];
+    assert!(unsafe {
+        balatro_evaluate_poker_hands(cards.as_ptr(), cards.len(), counts.as_mut_ptr())
+    });
+    assert_eq!(counts[PokerHand::FullHouse as usize], 1);
+    assert_eq!(counts[PokerHand::Flush as usize], 0);
+}
+
+#[test]
+fn null_pointers_fail_instead_of_crashing() {
+    let mut odds = [0.0; BALATRO_POKER_HAND_COUNT];
+    unsafe {
+        assert_eq!(balatro_best_hand(ptr::null(), 3), -1);
+        assert!(balatro_deck_from_preset(ptr::null(), 0).is_null());
+        assert!(!balatro_draw_trial_best(
+            ptr::null(),
+            8,
+            10,
+            odds.as_mut_ptr(),
+            ptr::null_mut()
+        ));
+        assert_eq!(balatro_deck_size(ptr::null()), 0);
+        balatro_deck_free(ptr::null_mut());
+    }
+}
+
+#[test]
+fn draw_trial_matches_the_rust_results() {
+    let deck = balatro_deck_new();
+    let mut odds = [BalatroOdds {
+        count: 0,
+        probability: 0.0,
+        ci_low: 0.0,
+        ci_high: 0.0,
+    }; BALATRO_POKER_HAND_COUNT];
+    assert!(unsafe { balatro_draw_trial(deck, 8, 500, 11, odds.as_mut_ptr()) });
+    let results = draw_trial_results(&Deck::new(), 8, 500, 11, Threads::Single);
+    for (odds, result) in odds.iter().zip(results.results.iter()) {
+        assert_eq!(odds.count, result.count);
+        assert_eq!(odds.ci_low, result.ci.low);
+    }
+    unsafe { balatro_deck_free(deck) };
+}
+
+#[test]
+fn panics_return_the_failure_value() {
+    assert!(!guard(false, || panic!("deck ran out")));
+    assert_eq!(guard(0, || 52), 52);
+}
@@ -4,29 +4,101 @@ use crate::{
     card::Suit::{self, Clubs, Diamonds, Hearts, Spades, Wild, NONE},
     card::{Card, Rank},
 };
-use wasm_bindgen::prelude::wasm_bindgen;
 use serde::{Serialize, Deserialize};
 
+mod play;
+pub use play::Play;
+pub(crate) use play::for_each_combination;
+
 #[cfg(test)]
 mod test;
 
-#[wasm_bindgen]
-#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+/// A poker hand, ordered by Balatro's hand ranking so e.g. `Flush > Straight`.
+/// The serialized names are part of the JSON results and stay the same across
+/// versions, even if a variant is renamed. They are the one list of names, the
+/// TypeScript union is generated from them and the other copies are tested
+/// against them.
+#[allow(clippy::upper_case_acronyms)]
+#[cfg_attr(
+    all(target_arch = "wasm32", feature = "wasm"),
+    wasm_bindgen::prelude::wasm_bindgen,
+    derive(tsify::Tsify),
+    // The string union of the names, the enum above already takes PokerHand
+    serde(rename = "PokerHandName")
+)]
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum PokerHand {
+    // Not even a high card, when every card is a Stone card or there are no cards
+    #[serde(rename = "None")]
+    NONE,
+    #[serde(rename = "HighCard")]
+    HighCard,
+    #[serde(rename = "Pair")]
     Pair,
+    #[serde(rename = "TwoPair")]
     TwoPair,
+    #[serde(rename = "ThreeOfAKind")]
     ThreeOfAKind,
+    #[serde(rename = "Straight")]
     Straight,
+    #[serde(rename = "Flush")]
     Flush,
+    #[serde(rename = "FullHouse")]
     FullHouse,
+    #[serde(rename = "FourOfAKind")]
     FourOfAKind,
+    #[serde(rename = "StraightFlush")]
     StraightFlush,
+    #[serde(rename = "RoyalFlush")]
     RoyalFlush,
-    FlushHouse,
+    #[serde(rename = "FiveOfAKind")]
     FiveOfAKind,
+    #[serde(rename = "FlushHouse")]
+    FlushHouse,
+    #[serde(rename = "FlushFive")]
     FlushFive,
 }
 
+impl PokerHand {
+    // Every poker hand from the lowest to the highest ranked
+    pub const ALL: [PokerHand; 14] = [
+        PokerHand::NONE,
+        PokerHand::HighCard,
+        PokerHand::Pair,
+        PokerHand::TwoPair,
+        PokerHand::ThreeOfAKind,
+        PokerHand::Straight,
+        PokerHand::Flush,
+        PokerHand::FullHouse,
+        PokerHand::FourOfAKind,
+        PokerHand::StraightFlush,
+        PokerHand::RoyalFlush,
+        PokerHand::FiveOfAKind,
+        PokerHand::FlushHouse,
+        PokerHand::FlushFive,
+    ];
+
+    // The serialized name of the hand
+    pub fn to_str(self) -> &'static str {
+        match self {
+            PokerHand::NONE => "None",
+            PokerHand::HighCard => "HighCard",
+            PokerHand::Pair => "Pair",
+            PokerHand::TwoPair => "TwoPair",
+            PokerHand::ThreeOfAKind => "ThreeOfAKind",
+            PokerHand::Straight => "Straight",
+            PokerHand::Flush => "Flush",
+            PokerHand::FullHouse => "FullHouse",
+            PokerHand::FourOfAKind => "FourOfAKind",
+            PokerHand::StraightFlush => "StraightFlush",
+            PokerHand::RoyalFlush => "RoyalFlush",
+            PokerHand::FiveOfAKind => "FiveOfAKind",
+            PokerHand::FlushHouse => "FlushHouse",
+            PokerHand::FlushFive => "FlushFive",
+        }
+    }
+}
+
 
 
 #[derive(Debug, Clone)]
@@ -40,18 +112,15 @@ impl From<Vec<Card>> for Hand {
     }
 }
 
-fn create_hand_with_subbed_wilds(cards: &Vec<Card>) -> Hand {
+fn create_hand_with_subbed_wilds(cards: &[Card]) -> Hand {
     let mut new_cards = Vec::new();
     for card in cards.iter() {
         if card.suit == Wild {
             for suit in [Clubs, Diamonds, Hearts, Spades] {
-                new_cards.push(Card {
-                    rank: card.rank,
-                    suit,
-                });
+                new_cards.push(Card { suit, ..*card });
             }
         } else {
-            new_cards.push(card.clone());
+            new_cards.push(*card);
         }
     }
     Hand { cards: new_cards }
@@ -349,25 +418,23 @@ impl Hand {
             last_rank = card.rank;
             last_suit = card.suit;
         }
-        if have_non_flush_pairs_2 && have_non_flush_threes {
-            hand_map.insert(PokerHand::FullHouse, 1);
-        } else if have_non_flush_pairs && have_flush_threes.len() > 0 {
-            hand_map.insert(PokerHand::FullHouse, 1);
-        } else if have_non_flush_threes && have_flush_pairs.len() > 0 {
+        if (have_non_flush_pairs_2 && have_non_flush_threes)
+            || (have_non_flush_pairs && !have_flush_threes.is_empty())
+            || (have_non_flush_threes && !have_flush_pairs.is_empty())
+        {
             hand_map.insert(PokerHand::FullHouse, 1);
         }
 
         if have_flush_threes.len() > 1
-            || (have_flush_threes.len() > 0 && have_flush_pairs.len() > 0)
+            || (!have_flush_threes.is_empty() && !have_flush_pairs.is_empty())
         {
             hand_map.insert(PokerHand::FlushHouse, 1);
         } else {
             for _3suit in have_flush_threes.iter() {
-                if have_flush_pairs.contains_key(_3suit.0) {
-                    hand_map.insert(PokerHand::FlushHouse, 1);
-                } else if _3suit.0 == &Wild && have_flush_pairs.len() > 0 {
-                    hand_map.insert(PokerHand::FlushHouse, 1);
-                } else if have_flush_pairs.contains_key(&Wild) {
+                if have_flush_pairs.contains_key(_3suit.0)
+                    || (_3suit.0 == &Wild && !have_flush_pairs.is_empty())
+                    || have_flush_pairs.contains_key(&Wild)
+                {
                     hand_map.insert(PokerHand::FlushHouse, 1);
                 }
             }
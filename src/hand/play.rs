@@ -0,0 +1,306 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::card::{Card, Rank, Suit};
+use crate::hand::{Hand, PokerHand};
+
+/// The cards chosen to be played from a hand.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Play {
+    pub hand: PokerHand,
+    // Every card that was played
+    pub cards: Vec<Card>,
+    // The played cards that make up the poker hand in played order, only
+    // these and Stone cards score unless a joker like Splash makes every
+    // played card score
+    pub forming: Vec<Card>,
+}
+
+impl Play {
+    // Works out the poker hand and the cards forming it for the played cards
+    pub fn new(cards: Vec<Card>) -> Play {
+        let best = Hand::from(cards.clone()).best_play();
+        let mut remaining = best.forming;
+        let forming = cards
+            .iter()
+            .filter(|card| {
+                if card.is_stone() {
+                    return true;
+                }
+                match remaining.iter().position(|x| x == *card) {
+                    Some(pos) => {
+                        remaining.remove(pos);
+                        true
+                    }
+                    None => false,
+                }
+            })
+            .copied()
+            .collect();
+        Play {
+            hand: best.hand,
+            cards,
+            forming,
+        }
+    }
+
+    // Like new, but with Four Fingers a Flush or a Straight only takes 4 of
+    // the cards. Both at once make a Straight Flush, even when they share
+    // only some of their cards, like in game.
+    pub fn with_four_fingers(cards: Vec<Card>) -> Play {
+        let play = Play::new(cards);
+        let flush = four_card_flush(&play.cards);
+        let straight = four_card_straight(&play.cards);
+        let hand = match (&flush, &straight) {
+            (Some(_), Some(_)) => PokerHand::StraightFlush,
+            (Some(_), None) if play.hand == PokerHand::FullHouse => PokerHand::FlushHouse,
+            (Some(_), None) if play.hand == PokerHand::FiveOfAKind => PokerHand::FlushFive,
+            (Some(_), None) => PokerHand::Flush,
+            (None, Some(_)) => PokerHand::Straight,
+            (None, None) => return play,
+        };
+        if hand <= play.hand {
+            return play;
+        }
+        // Every card of a Full House or Five of a Kind already forms it
+        let every_card = matches!(hand, PokerHand::FlushHouse | PokerHand::FlushFive);
+        let forming: Vec<usize> = flush.into_iter().chain(straight).flatten().collect();
+        Play {
+            hand,
+            forming: play
+                .cards
+                .iter()
+                .enumerate()
+                .filter(|(i, card)| every_card || card.is_stone() || forming.contains(i))
+                .map(|(_, card)| *card)
+                .collect(),
+            cards: play.cards,
+        }
+    }
+}
+
+impl PokerHand {
+    // Whether every set of cards making this hand also makes `other`, e.g. a
+    // Full House contains a Three of a Kind, a Two Pair and a Pair. Every hand
+    // contains itself and, apart from no hand at all, a High Card.
+    pub fn contains(self, other: PokerHand) -> bool {
+        if self == other || (self != PokerHand::NONE && other == PokerHand::HighCard) {
+            return true;
+        }
+        let contained: &[PokerHand] = match self {
+            PokerHand::TwoPair => &[PokerHand::Pair],
+            PokerHand::ThreeOfAKind => &[PokerHand::Pair],
+            PokerHand::FullHouse => &[PokerHand::Pair, PokerHand::TwoPair, PokerHand::ThreeOfAKind],
+            PokerHand::FourOfAKind => &[PokerHand::Pair, PokerHand::ThreeOfAKind],
+            PokerHand::StraightFlush | PokerHand::RoyalFlush => &[
+                PokerHand::Straight,
+                PokerHand::Flush,
+                PokerHand::StraightFlush,
+            ],
+            PokerHand::FiveOfAKind => &[
+                PokerHand::Pair,
+                PokerHand::ThreeOfAKind,
+                PokerHand::FourOfAKind,
+            ],
+            PokerHand::FlushHouse => &[
+                PokerHand::Pair,
+                PokerHand::TwoPair,
+                PokerHand::ThreeOfAKind,
+                PokerHand::Flush,
+                PokerHand::FullHouse,
+            ],
+            PokerHand::FlushFive => &[
+                PokerHand::Pair,
+                PokerHand::ThreeOfAKind,
+                PokerHand::Flush,
+                PokerHand::FourOfAKind,
+                PokerHand::FiveOfAKind,
+            ],
+            _ => &[],
+        };
+        contained.contains(&other)
+    }
+
+    // How many cards it takes to make the hand
+    pub fn card_count(self) -> usize {
+        match self {
+            PokerHand::NONE | PokerHand::HighCard => 1,
+            PokerHand::Pair => 2,
+            PokerHand::ThreeOfAKind => 3,
+            PokerHand::TwoPair | PokerHand::FourOfAKind => 4,
+            _ => 5,
+        }
+    }
+}
+
+// Calls `f` with every combination of `k` indices out of `0..n`
+pub(crate) fn for_each_combination(n: usize, k: usize, mut f: impl FnMut(&[usize])) {
+    if k > n {
+        return;
+    }
+    let mut indices: Vec<usize> = (0..k).collect();
+    loop {
+        f(&indices);
+        // Find the rightmost index that can still move right
+        let mut i = k;
+        while i > 0 && indices[i - 1] == i - 1 + n - k {
+            i -= 1;
+        }
+        if i == 0 {
+            return;
+        }
+        indices[i - 1] += 1;
+        for j in i..k {
+            indices[j] = indices[j - 1] + 1;
+        }
+    }
+}
+
+const SUITS: [Suit; 4] = [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades];
+
+fn ranked_cards(cards: &[Card]) -> Vec<Card> {
+    cards
+        .iter()
+        .filter(|card| !card.is_stone())
+        .copied()
+        .collect()
+}
+
+// The evaluator reports an ace high straight flush as a Straight Flush, a
+// Wild card counts as every suit
+fn has_royal_flush(cards: &[Card]) -> bool {
+    let ranks = [Rank::Ten, Rank::Jack, Rank::Queen, Rank::King, Rank::Ace];
+    SUITS.iter().any(|suit| {
+        ranks.iter().all(|rank| {
+            cards
+                .iter()
+                .any(|card| card.rank == *rank && (card.suit == *suit || card.suit == Suit::Wild))
+        })
+    })
+}
+
+// Indices of the cards making a Flush of at least 4 cards
+fn four_card_flush(cards: &[Card]) -> Option<Vec<usize>> {
+    SUITS
+        .iter()
+        .map(|suit| {
+            cards
+                .iter()
+                .enumerate()
+                .filter(|(_, card)| {
+                    !card.is_stone() && (card.suit == *suit || card.suit == Suit::Wild)
+                })
+                .map(|(i, _)| i)
+                .collect::<Vec<usize>>()
+        })
+        .find(|indices| indices.len() >= 4)
+}
+
+// Indices of one card of each rank of a Straight of 4 ranks in a row, an Ace
+// can be low or high
+fn four_card_straight(cards: &[Card]) -> Option<Vec<usize>> {
+    let find = |rank: usize| {
+        cards
+            .iter()
+            .position(|card| !card.is_stone() && card.rank.to_int() == rank)
+    };
+    (1..=11).find_map(|low| {
+        (low..low + 4)
+            .map(|rank| find(if rank == 1 { 14 } else { rank }))
+            .collect()
+    })
+}
+
+fn rank_total(cards: &[Card]) -> usize {
+    cards.iter().map(|card| card.rank.to_int()).sum()
+}
+
+impl Hand {
+    // Every poker hand the cards contain, including the ones a higher hand
+    // implies, e.g. a Straight Flush also contains a Straight and a Flush and
+    // any card is a High Card. Stone cards have no rank or suit, so a hand of
+    // only Stone cards contains no hand at all.
+    pub fn contained_hands(&self) -> HashMap<PokerHand, u32> {
+        let mut ranked = Hand::from(ranked_cards(&self.cards));
+        if ranked.cards.is_empty() {
+            return HashMap::from([(PokerHand::NONE, 1)]);
+        }
+        // The evaluator leaves out most of the hands a higher one implies
+        let mut found = ranked.evaluate_poker_hands();
+        if has_royal_flush(&ranked.cards) {
+            found.insert(PokerHand::RoyalFlush, 1);
+        }
+        found.entry(PokerHand::HighCard).or_insert(1);
+        PokerHand::ALL
+            .into_iter()
+            .filter(|hand| found.keys().any(|higher| higher.contains(*hand)))
+            .map(|hand| (hand, found.get(&hand).copied().unwrap_or(1)))
+            .collect()
+    }
+
+    // The highest ranked poker hand that can be made from the cards
+    pub fn best_hand(&self) -> PokerHand {
+        self.contained_hands()
+            .into_keys()
+            .max()
+            .unwrap_or(PokerHand::NONE)
+    }
+
+    // Picks the cards that make the best poker hand, using as few cards as
+    // the hand needs and preferring higher ranks. With no poker hand the
+    // highest card is played on its own.
+    pub fn best_play(&self) -> Play {
+        let ranked = ranked_cards(&self.cards);
+        let best = self.best_hand();
+        let single = |cards: &[Card]| -> Vec<Card> {
+            cards
+                .iter()
+                .max_by_key(|card| card.rank)
+                .map(|card| vec![*card])
+                .unwrap_or_default()
+        };
+        match best {
+            PokerHand::NONE => {
+                let cards = single(&self.cards);
+                return Play {
+                    hand: best,
+                    cards: cards.clone(),
+                    forming: cards,
+                };
+            }
+            PokerHand::HighCard => {
+                let cards = single(&ranked);
+                return Play {
+                    hand: best,
+                    cards: cards.clone(),
+                    forming: cards,
+                };
+            }
+            _ => {}
+        }
+
+        let mut chosen: Vec<Card> = Vec::new();
+        // Only up to 5 cards can be played, if the hand needs fewer cards than
+        // we think (e.g. because of wilds) the smallest size is found first
+        for count in best.card_count()..=5.min(ranked.len()) {
+            for_each_combination(ranked.len(), count, |indices| {
+                let cards: Vec<Card> = indices.iter().map(|&i| ranked[i]).collect();
+                if rank_total(&cards) <= rank_total(&chosen) {
+                    return;
+                }
+                if Hand::from(cards.clone()).best_hand() == best {
+                    chosen = cards;
+                }
+            });
+            if !chosen.is_empty() {
+                break;
+            }
+        }
+        Play {
+            hand: best,
+            cards: chosen.clone(),
+            forming: chosen,
+        }
+    }
+}
@@ -3,7 +3,7 @@ use common_macros::hash_map;
 #[cfg(test)]
 use super::*;
 use crate::{
-    card::Card,
+    card::{Card, Enhancement},
     hand::{Hand, PokerHand},
 };
 use std::collections::HashMap;
@@ -33,26 +33,21 @@ fn assert_expected_results(results: &HashMap<PokerHand, u32>, expected: &HashMap
 fn test_hand_correctness(cards: Vec<Card>, expected: &HashMap<PokerHand, u32>) {
     let mut hand = Hand::from(cards);
     let results = hand.evaluate_poker_hands();
-    assert_expected_results(&results, &expected);
+    assert_expected_results(&results, expected);
 }
 #[test]
 fn it_evaluates_hand_correctly_1() {
-    let cards: Vec<Card>;
-
-    #[rustfmt::skip]
-        {
-            cards = vec![
-                Card { rank: Rank::Two  , suit: Clubs    },
-                Card { rank: Rank::Five , suit: Clubs    },
-                Card { rank: Rank::Three, suit: Clubs    },
-                Card { rank: Rank::Four , suit: Wild     },
-                Card { rank: Rank::Eight, suit: Diamonds },
-                Card { rank: Rank::Eight, suit: Diamonds },
-                Card { rank: Rank::Eight, suit: Clubs    },
-                Card { rank: Rank::Ace  , suit: Wild     },
-                Card { rank: Rank::Ace  , suit: Diamonds },
-            ];
-        }
+    let cards: Vec<Card> = vec![
+        Card::new(Rank::Two, Clubs),
+        Card::new(Rank::Five, Clubs),
+        Card::new(Rank::Three, Clubs),
+        Card::new(Rank::Four, Wild),
+        Card::new(Rank::Eight, Diamonds),
+        Card::new(Rank::Eight, Diamonds),
+        Card::new(Rank::Eight, Clubs),
+        Card::new(Rank::Ace, Wild),
+        Card::new(Rank::Ace, Diamonds),
+    ];
 
     let expected: HashMap<PokerHand, u32> = hash_map! {
         PokerHand::Pair => 1,
@@ -68,22 +63,17 @@ fn it_evaluates_hand_correctly_1() {
 
 #[test]
 fn it_evaluates_hand_correctly_2() {
-    let cards: Vec<Card>;
-
-    #[rustfmt::skip]
-        {
-            cards = vec![
-                Card { rank: Rank::Two  , suit: Clubs    },
-                Card { rank: Rank::Five , suit: Clubs    },
-                Card { rank: Rank::Three, suit: Clubs    },
-                Card { rank: Rank::Four , suit: Hearts   },
-                Card { rank: Rank::Eight, suit: Diamonds },
-                Card { rank: Rank::Eight, suit: Diamonds },
-                Card { rank: Rank::Eight, suit: Clubs    },
-                Card { rank: Rank::Ace  , suit: Wild     },
-                Card { rank: Rank::Ace  , suit: Diamonds },
-            ];
-        }
+    let cards: Vec<Card> = vec![
+        Card::new(Rank::Two, Clubs),
+        Card::new(Rank::Five, Clubs),
+        Card::new(Rank::Three, Clubs),
+        Card::new(Rank::Four, Hearts),
+        Card::new(Rank::Eight, Diamonds),
+        Card::new(Rank::Eight, Diamonds),
+        Card::new(Rank::Eight, Clubs),
+        Card::new(Rank::Ace, Wild),
+        Card::new(Rank::Ace, Diamonds),
+    ];
 
     let expected: HashMap<PokerHand, u32> = hash_map! {
         PokerHand::Pair => 1,
@@ -97,19 +87,14 @@ fn it_evaluates_hand_correctly_2() {
 }
 #[test]
 fn it_evaluates_hand_correctly_3() {
-    let cards: Vec<Card>;
-
-    #[rustfmt::skip]
-        {
-            cards = vec![
-                Card { rank: Rank::Two  , suit: Clubs    },
-                Card { rank: Rank::Five , suit: Clubs    },
-                Card { rank: Rank::Three, suit: Clubs    },
-                Card { rank: Rank::Four , suit: Clubs    },
-                Card { rank: Rank::Four , suit: Hearts   },
-                Card { rank: Rank::Ace  , suit: Diamonds },
-            ];
-        }
+    let cards: Vec<Card> = vec![
+        Card::new(Rank::Two, Clubs),
+        Card::new(Rank::Five, Clubs),
+        Card::new(Rank::Three, Clubs),
+        Card::new(Rank::Four, Clubs),
+        Card::new(Rank::Four, Hearts),
+        Card::new(Rank::Ace, Diamonds),
+    ];
 
     let expected: HashMap<PokerHand, u32> = hash_map! {
         PokerHand::Pair => 1,
@@ -119,18 +104,13 @@ fn it_evaluates_hand_correctly_3() {
 }
 #[test]
 fn it_evaluates_hand_correctly_4() {
-    let cards: Vec<Card>;
-
-    #[rustfmt::skip]
-        {
-            cards = vec![
-                Card { rank: Rank::Two  , suit: Clubs    },
-                Card { rank: Rank::Five , suit: Diamonds },
-                Card { rank: Rank::Three, suit: Diamonds },
-                Card { rank: Rank::Four , suit: Diamonds },
-                Card { rank: Rank::Ace  , suit: Diamonds },
-            ];
-        }
+    let cards: Vec<Card> = vec![
+        Card::new(Rank::Two, Clubs),
+        Card::new(Rank::Five, Diamonds),
+        Card::new(Rank::Three, Diamonds),
+        Card::new(Rank::Four, Diamonds),
+        Card::new(Rank::Ace, Diamonds),
+    ];
 
     let expected: HashMap<PokerHand, u32> = hash_map! {
         PokerHand::Straight => 1,
@@ -139,18 +119,13 @@ fn it_evaluates_hand_correctly_4() {
 }
 #[test]
 fn it_evaluates_5_wild_flush_five() {
-    let cards: Vec<Card>;
-
-    #[rustfmt::skip]
-        {
-            cards = vec![
-                Card { rank: Rank::Two  , suit: Wild    },
-                Card { rank: Rank::Two  , suit: Wild    },
-                Card { rank: Rank::Two  , suit: Wild    },
-                Card { rank: Rank::Two  , suit: Wild    },
-                Card { rank: Rank::Two  , suit: Wild    },
-            ];
-        }
+    let cards: Vec<Card> = vec![
+        Card::new(Rank::Two, Wild),
+        Card::new(Rank::Two, Wild),
+        Card::new(Rank::Two, Wild),
+        Card::new(Rank::Two, Wild),
+        Card::new(Rank::Two, Wild),
+    ];
 
     let expected: HashMap<PokerHand, u32> = hash_map! {
         PokerHand::FlushFive => 1,
@@ -163,18 +138,13 @@ fn it_evaluates_5_wild_flush_five() {
 
 #[test]
 fn it_evaluates_5_of_a_kind_with_wilds() {
-    let cards: Vec<Card>;
-
-    #[rustfmt::skip]
-        {
-            cards = vec![
-                Card { rank: Rank::Two  , suit: Wild    },
-                Card { rank: Rank::Two  , suit: Clubs   },
-                Card { rank: Rank::Two  , suit: Hearts  },
-                Card { rank: Rank::Two  , suit: Wild    },
-                Card { rank: Rank::Two  , suit: Wild    },
-            ];
-        }
+    let cards: Vec<Card> = vec![
+        Card::new(Rank::Two, Wild),
+        Card::new(Rank::Two, Clubs),
+        Card::new(Rank::Two, Hearts),
+        Card::new(Rank::Two, Wild),
+        Card::new(Rank::Two, Wild),
+    ];
 
     let expected: HashMap<PokerHand, u32> = hash_map! {
         PokerHand::FiveOfAKind => 1,
@@ -186,19 +156,14 @@ fn it_evaluates_5_of_a_kind_with_wilds() {
 }
 #[test]
 fn it_evaluates_5_of_a_kind_and_flush_five_with_wilds() {
-    let cards: Vec<Card>;
-
-    #[rustfmt::skip]
-        {
-            cards = vec![
-                Card { rank: Rank::Two  , suit: Wild    },
-                Card { rank: Rank::Two  , suit: Clubs   },
-                Card { rank: Rank::Two  , suit: Hearts  },
-                Card { rank: Rank::Two  , suit: Wild    },
-                Card { rank: Rank::Two  , suit: Wild    },
-                Card { rank: Rank::Two  , suit: Wild    },
-            ];
-        }
+    let cards: Vec<Card> = vec![
+        Card::new(Rank::Two, Wild),
+        Card::new(Rank::Two, Clubs),
+        Card::new(Rank::Two, Hearts),
+        Card::new(Rank::Two, Wild),
+        Card::new(Rank::Two, Wild),
+        Card::new(Rank::Two, Wild),
+    ];
 
     let expected: HashMap<PokerHand, u32> = hash_map! {
         PokerHand::FlushFive => 1,
@@ -211,19 +176,14 @@ fn it_evaluates_5_of_a_kind_and_flush_five_with_wilds() {
 }
 #[test]
 fn it_evalutes_flush_house() {
-    let cards: Vec<Card>;
-
-    #[rustfmt::skip]
-        {
-            cards = vec![
-                Card { rank: Rank::Two  , suit: Wild    },
-                Card { rank: Rank::Two  , suit: Wild    },
-                Card { rank: Rank::Three  , suit: Clubs    },
-                Card { rank: Rank::Three  , suit: Wild    },
-                Card { rank: Rank::Three  , suit: Wild    },
-                Card { rank: Rank::Ace  , suit: Clubs    },
-            ];
-        }
+    let cards: Vec<Card> = vec![
+        Card::new(Rank::Two, Wild),
+        Card::new(Rank::Two, Wild),
+        Card::new(Rank::Three, Clubs),
+        Card::new(Rank::Three, Wild),
+        Card::new(Rank::Three, Wild),
+        Card::new(Rank::Ace, Clubs),
+    ];
 
     let expected: HashMap<PokerHand, u32> = hash_map! {
         PokerHand::FlushHouse => 1,
@@ -238,17 +198,13 @@ fn it_evalutes_flush_house() {
 
 #[test]
 fn test_simple_flush() {
-    let cards: Vec<Card>;
-    #[rustfmt::skip]
-        {
-            cards = vec![
-                Card { rank: Rank::Two, suit: Hearts },
-                Card { rank: Rank::Three, suit: Hearts },
-                Card { rank: Rank::Five, suit: Hearts },
-                Card { rank: Rank::Seven, suit: Hearts },
-                Card { rank: Rank::Nine, suit: Hearts },
-            ];
-        }
+    let cards: Vec<Card> = vec![
+        Card::new(Rank::Two, Hearts),
+        Card::new(Rank::Three, Hearts),
+        Card::new(Rank::Five, Hearts),
+        Card::new(Rank::Seven, Hearts),
+        Card::new(Rank::Nine, Hearts),
+    ];
     let expected = hash_map! {
         PokerHand::Flush => 1,
     };
@@ -257,17 +213,13 @@ fn test_simple_flush() {
 
 #[test]
 fn test_simple_straight() {
-    let cards: Vec<Card>;
-    #[rustfmt::skip]
-        {
-            cards = vec![
-                Card { rank: Rank::Ten, suit: Clubs },
-                Card { rank: Rank::Jack, suit: Diamonds },
-                Card { rank: Rank::Queen, suit: Hearts },
-                Card { rank: Rank::King, suit: Spades },
-                Card { rank: Rank::Ace, suit: Wild }, // Acting as Ace of any suit
-            ];
-        }
+    let cards: Vec<Card> = vec![
+        Card::new(Rank::Ten, Clubs),
+        Card::new(Rank::Jack, Diamonds),
+        Card::new(Rank::Queen, Hearts),
+        Card::new(Rank::King, Spades),
+        Card::new(Rank::Ace, Wild), // Acting as Ace of any suit
+    ];
     let expected = hash_map! {
         PokerHand::Straight => 1,
     };
@@ -276,18 +228,14 @@ fn test_simple_straight() {
 
 #[test]
 fn test_with_multiple_wild_cards_forming_straight_flush() {
-    let cards: Vec<Card>;
-    #[rustfmt::skip]
-        {
-            cards = vec![
-                Card { rank: Rank::Six, suit: Wild },
-                Card { rank: Rank::Seven, suit: Wild },
-                Card { rank: Rank::Eight, suit: Clubs },
-                Card { rank: Rank::Eight, suit: Clubs },
-                Card { rank: Rank::Nine, suit: Clubs },
-                Card { rank: Rank::Ten, suit: Clubs },
-            ];
-        }
+    let cards: Vec<Card> = vec![
+        Card::new(Rank::Six, Wild),
+        Card::new(Rank::Seven, Wild),
+        Card::new(Rank::Eight, Clubs),
+        Card::new(Rank::Eight, Clubs),
+        Card::new(Rank::Nine, Clubs),
+        Card::new(Rank::Ten, Clubs),
+    ];
     let expected = hash_map! {
         PokerHand::StraightFlush => 1,
         PokerHand::Flush=>1,
@@ -298,17 +246,13 @@ fn test_with_multiple_wild_cards_forming_straight_flush() {
 
 #[test]
 fn test_for_natural_full_house_with_single_wild() {
-    let cards: Vec<Card>;
-    #[rustfmt::skip]
-        {
-            cards = vec![
-                Card { rank: Rank::Jack, suit: Diamonds },
-                Card { rank: Rank::Jack, suit: Spades },
-                Card { rank: Rank::Jack, suit: Clubs },
-                Card { rank: Rank::Nine, suit: Hearts },
-                Card { rank: Rank::Nine, suit: Wild }, // Acting as Nine of any suit
-            ];
-        }
+    let cards: Vec<Card> = vec![
+        Card::new(Rank::Jack, Diamonds),
+        Card::new(Rank::Jack, Spades),
+        Card::new(Rank::Jack, Clubs),
+        Card::new(Rank::Nine, Hearts),
+        Card::new(Rank::Nine, Wild), // Acting as Nine of any suit
+    ];
     let expected = hash_map! {
         PokerHand::FullHouse => 1,
         PokerHand::Pair => 1,
@@ -321,26 +265,11 @@ fn test_for_natural_full_house_with_single_wild() {
 #[test]
 fn test_flush_with_wild_cards_as_filler() {
     let cards: Vec<Card> = vec![
-        Card {
-            rank: Rank::Two,
-            suit: Spades,
-        },
-        Card {
-            rank: Rank::Four,
-            suit: Spades,
-        },
-        Card {
-            rank: Rank::Ace,
-            suit: Wild,
-        }, // Acting as Spades
-        Card {
-            rank: Rank::Eight,
-            suit: Spades,
-        },
-        Card {
-            rank: Rank::King,
-            suit: Wild,
-        }, // Acting as Spades
+        Card::new(Rank::Two, Spades),
+        Card::new(Rank::Four, Spades),
+        Card::new(Rank::Ace, Wild), // Acting as Spades
+        Card::new(Rank::Eight, Spades),
+        Card::new(Rank::King, Wild), // Acting as Spades
     ];
     let expected = hash_map! {
         PokerHand::Flush => 1,
@@ -350,30 +279,12 @@ fn test_flush_with_wild_cards_as_filler() {
 #[test]
 fn test_higher_flush_with_wild_cards() {
     let cards: Vec<Card> = vec![
-        Card {
-            rank: Rank::Nine,
-            suit: Hearts,
-        },
-        Card {
-            rank: Rank::Ten,
-            suit: Hearts,
-        },
-        Card {
-            rank: Rank::Jack,
-            suit: Hearts,
-        },
-        Card {
-            rank: Rank::Queen,
-            suit: Wild,
-        },
-        Card {
-            rank: Rank::King,
-            suit: Wild,
-        },
-        Card {
-            rank: Rank::Ace,
-            suit: Spades,
-        },
+        Card::new(Rank::Nine, Hearts),
+        Card::new(Rank::Ten, Hearts),
+        Card::new(Rank::Jack, Hearts),
+        Card::new(Rank::Queen, Wild),
+        Card::new(Rank::King, Wild),
+        Card::new(Rank::Ace, Spades),
     ];
     let expected = hash_map! {
         PokerHand::StraightFlush => 1,
@@ -385,26 +296,11 @@ fn test_higher_flush_with_wild_cards() {
 #[test]
 fn test_natural_full_house_vs_wild_full_house() {
     let cards: Vec<Card> = vec![
-        Card {
-            rank: Rank::Jack,
-            suit: Diamonds,
-        },
-        Card {
-            rank: Rank::Jack,
-            suit: Spades,
-        },
-        Card {
-            rank: Rank::Jack,
-            suit: Clubs,
-        },
-        Card {
-            rank: Rank::Nine,
-            suit: Hearts,
-        },
-        Card {
-            rank: Rank::Nine,
-            suit: Wild,
-        },
+        Card::new(Rank::Jack, Diamonds),
+        Card::new(Rank::Jack, Spades),
+        Card::new(Rank::Jack, Clubs),
+        Card::new(Rank::Nine, Hearts),
+        Card::new(Rank::Nine, Wild),
     ];
     let expected = hash_map! {
         PokerHand::FullHouse => 1,
@@ -416,15 +312,11 @@ fn test_natural_full_house_vs_wild_full_house() {
 }
 #[test]
 fn test_3_card_hand_full_house() {
-    let cards: Vec<Card>;
-    #[rustfmt::skip]
-    {
-        cards = vec![
-            Card { rank: Rank::Ten, suit: Hearts, },
-            Card { rank: Rank::Ten, suit: Hearts, },
-            Card { rank: Rank::Ten, suit: Hearts, },
-        ];
-    }
+    let cards: Vec<Card> = vec![
+        Card::new(Rank::Ten, Hearts),
+        Card::new(Rank::Ten, Hearts),
+        Card::new(Rank::Ten, Hearts),
+    ];
     let expected = hash_map! {
         PokerHand::FullHouse => 0,
         PokerHand::Pair => 1,
@@ -432,3 +324,142 @@ fn test_3_card_hand_full_house() {
     };
     test_hand_correctness(cards, &expected);
 }
+
+#[test]
+fn best_play_uses_only_the_cards_forming_the_hand() {
+    let cards: Vec<Card> = vec![
+        Card::new(Rank::Two, Clubs),
+        Card::new(Rank::King, Hearts),
+        Card::new(Rank::Five, Spades),
+        Card::new(Rank::King, Diamonds),
+        Card::new(Rank::Two, Hearts),
+        Card::new(Rank::Nine, Clubs),
+        Card::new(Rank::Ace, Spades),
+        Card::new(Rank::Eight, Diamonds),
+    ];
+    let play = Hand::from(cards).best_play();
+    assert_eq!(play.hand, PokerHand::TwoPair);
+    assert_eq!(play.cards.len(), 4);
+    assert_eq!(
+        play.cards.iter().filter(|c| c.rank == Rank::King).count(),
+        2
+    );
+    assert_eq!(play.cards.iter().filter(|c| c.rank == Rank::Two).count(), 2);
+}
+
+#[test]
+fn best_play_prefers_higher_ranks() {
+    let cards: Vec<Card> = vec![
+        Card::new(Rank::Three, Clubs),
+        Card::new(Rank::Three, Hearts),
+        Card::new(Rank::Queen, Spades),
+        Card::new(Rank::Queen, Diamonds),
+        Card::new(Rank::Seven, Clubs),
+    ];
+    let play = Hand::from(cards.clone()).best_play();
+    assert_eq!(play.hand, PokerHand::TwoPair);
+
+    let play = Hand::from(cards[2..].to_vec()).best_play();
+    assert_eq!(play.hand, PokerHand::Pair);
+    assert!(play.cards.iter().all(|c| c.rank == Rank::Queen));
+
+    // No poker hand, so the highest card is played
+    let play = Hand::from(vec![cards[0], cards[2], cards[4]]).best_play();
+    assert_eq!(play.hand, PokerHand::HighCard);
+    assert_eq!(play.cards, vec![cards[2]]);
+}
+
+#[test]
+fn stone_cards_have_no_hand_but_always_score() {
+    let stone = Card::new(Rank::Ace, Spades).with_enhancement(Enhancement::Stone);
+    let play = Hand::from(vec![stone, stone]).best_play();
+    assert_eq!(play.hand, PokerHand::NONE);
+    assert_eq!(play.forming, vec![stone]);
+
+    let cards = vec![
+        Card::new(Rank::Four, Clubs),
+        stone,
+        Card::new(Rank::Four, Hearts),
+    ];
+    let hand = Hand::from(cards.clone());
+    // The Stone card's rank doesn't make a pair of aces with anything
+    assert_eq!(hand.best_hand(), PokerHand::Pair);
+    let play = Play::new(cards.clone());
+    assert_eq!(play.hand, PokerHand::Pair);
+    assert_eq!(play.forming, cards);
+}
+
+#[test]
+fn poker_hands_are_ordered_by_ranking() {
+    let mut sorted = PokerHand::ALL;
+    sorted.sort();
+    assert_eq!(sorted, PokerHand::ALL);
+    assert!(PokerHand::Flush > PokerHand::Straight);
+    assert!(PokerHand::FiveOfAKind > PokerHand::RoyalFlush);
+    assert!(PokerHand::FlushHouse > PokerHand::FiveOfAKind);
+    assert!(PokerHand::HighCard > PokerHand::NONE);
+}
+
+#[test]
+fn poker_hands_contain_smaller_hands() {
+    assert!(PokerHand::FullHouse.contains(PokerHand::ThreeOfAKind));
+    assert!(PokerHand::FullHouse.contains(PokerHand::TwoPair));
+    assert!(PokerHand::FullHouse.contains(PokerHand::Pair));
+    assert!(PokerHand::RoyalFlush.contains(PokerHand::StraightFlush));
+    assert!(PokerHand::FlushFive.contains(PokerHand::Flush));
+    assert!(!PokerHand::Straight.contains(PokerHand::Pair));
+    assert!(!PokerHand::Pair.contains(PokerHand::FullHouse));
+    assert!(!PokerHand::NONE.contains(PokerHand::HighCard));
+
+    // A hand only contains hands that rank at most as high
+    for hand in PokerHand::ALL {
+        assert!(hand.contains(hand));
+        for other in PokerHand::ALL {
+            if hand.contains(other) {
+                assert!(other <= hand, "{:?} contains {:?}", hand, other);
+            }
+        }
+    }
+}
+
+#[test]
+fn contained_hands_include_the_hands_they_imply() {
+    let straight_flush: Vec<Card> = (5..=9)
+        .map(|rank| Card::new(Rank::from_int(rank), Hearts))
+        .collect();
+    let contained = Hand::from(straight_flush).contained_hands();
+    for hand in [
+        PokerHand::HighCard,
+        PokerHand::Straight,
+        PokerHand::Flush,
+        PokerHand::StraightFlush,
+    ] {
+        assert!(contained.contains_key(&hand), "{:?}", hand);
+    }
+    assert_eq!(contained.len(), 4);
+
+    let full_deck = Hand::from(crate::deck::Deck::new().into_cards());
+    let contained = full_deck.contained_hands();
+    for hand in PokerHand::ALL {
+        let expected = !matches!(
+            hand,
+            PokerHand::NONE | PokerHand::FiveOfAKind | PokerHand::FlushHouse | PokerHand::FlushFive
+        );
+        assert_eq!(contained.contains_key(&hand), expected, "{:?}", hand);
+    }
+    assert_eq!(full_deck.best_hand(), PokerHand::RoyalFlush);
+}
+
+#[test]
+fn poker_hands_serialize_with_stable_names() {
+    assert_eq!(
+        serde_json::to_string(&PokerHand::ThreeOfAKind).unwrap(),
+        "\"ThreeOfAKind\""
+    );
+    assert_eq!(serde_json::to_string(&PokerHand::NONE).unwrap(), "\"None\"");
+    for hand in PokerHand::ALL {
+        let json = serde_json::to_string(&hand).unwrap();
+        assert_eq!(serde_json::from_str::<PokerHand>(&json).unwrap(), hand);
+        assert_eq!(json, format!("\"{}\"", hand.to_str()));
+    }
+}
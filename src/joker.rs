@@ -1,4 +1,264 @@
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+use serde::{Deserialize, Serialize};
+
+use std::str::FromStr;
+
+use crate::card::{Card, Rank, Suit};
+use crate::hand::PokerHand;
+use crate::scoring::levels::HandLevels;
+use crate::scoring::{Effect, JokerEffect, ScoringContext};
+
+// The variants are named after the jokers in game, including the plain Joker
+#[allow(clippy::enum_variant_names)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Joker {
-    FourFingers
+    // Flushes and Straights can be made with 4 cards
+    FourFingers,
+    // +4 mult
+    Joker,
+    // +3 mult for each scored card of a suit
+    Greedy,
+    Lusty,
+    Wrathful,
+    Gluttonous,
+    // +mult if the played hand contains the poker hand
+    Jolly,
+    Zany,
+    Mad,
+    Crazy,
+    Droll,
+    // +chips if the played hand contains the poker hand
+    Sly,
+    Wily,
+    Clever,
+    Devious,
+    Crafty,
+    // xmult if the played hand contains the poker hand
+    TheDuo,
+    TheTrio,
+    TheFamily,
+    TheOrder,
+    TheTribe,
+    // Every played card counts in scoring
+    Splash,
+    // Retrigger each played 2, 3, 4 or 5
+    Hack,
+    // Retrigger all played face cards
+    SockAndBuskin,
+    // Retrigger all played cards in the final hand of the round
+    Dusk,
+    // Retrigger the first scoring card 2 additional times
+    HangingChad,
+    // x1.5 mult for each King held in hand
+    Baron,
+    // +13 mult for each Queen held in hand
+    ShootTheMoon,
+    // 1 in 2 chance of x1.5 mult for each scored Heart
+    Bloodstone,
+    // 1 in 4 chance to level up the played poker hand
+    SpaceJoker,
+    // Doubles all listed probabilities
+    OopsAllSixes,
+}
+
+impl Joker {
+    pub const ALL: [Joker; 31] = [
+        Joker::FourFingers,
+        Joker::Joker,
+        Joker::Greedy,
+        Joker::Lusty,
+        Joker::Wrathful,
+        Joker::Gluttonous,
+        Joker::Jolly,
+        Joker::Zany,
+        Joker::Mad,
+        Joker::Crazy,
+        Joker::Droll,
+        Joker::Sly,
+        Joker::Wily,
+        Joker::Clever,
+        Joker::Devious,
+        Joker::Crafty,
+        Joker::TheDuo,
+        Joker::TheTrio,
+        Joker::TheFamily,
+        Joker::TheOrder,
+        Joker::TheTribe,
+        Joker::Splash,
+        Joker::Hack,
+        Joker::SockAndBuskin,
+        Joker::Dusk,
+        Joker::HangingChad,
+        Joker::Baron,
+        Joker::ShootTheMoon,
+        Joker::Bloodstone,
+        Joker::SpaceJoker,
+        Joker::OopsAllSixes,
+    ];
+
+    pub fn to_str(self) -> &'static str {
+        match self {
+            Joker::FourFingers => "Four Fingers",
+            Joker::Joker => "Joker",
+            Joker::Greedy => "Greedy Joker",
+            Joker::Lusty => "Lusty Joker",
+            Joker::Wrathful => "Wrathful Joker",
+            Joker::Gluttonous => "Gluttonous Joker",
+            Joker::Jolly => "Jolly Joker",
+            Joker::Zany => "Zany Joker",
+            Joker::Mad => "Mad Joker",
+            Joker::Crazy => "Crazy Joker",
+            Joker::Droll => "Droll Joker",
+            Joker::Sly => "Sly Joker",
+            Joker::Wily => "Wily Joker",
+            Joker::Clever => "Clever Joker",
+            Joker::Devious => "Devious Joker",
+            Joker::Crafty => "Crafty Joker",
+            Joker::TheDuo => "The Duo",
+            Joker::TheTrio => "The Trio",
+            Joker::TheFamily => "The Family",
+            Joker::TheOrder => "The Order",
+            Joker::TheTribe => "The Tribe",
+            Joker::Splash => "Splash",
+            Joker::Hack => "Hack",
+            Joker::SockAndBuskin => "Sock and Buskin",
+            Joker::Dusk => "Dusk",
+            Joker::HangingChad => "Hanging Chad",
+            Joker::Baron => "Baron",
+            Joker::ShootTheMoon => "Shoot the Moon",
+            Joker::Bloodstone => "Bloodstone",
+            Joker::SpaceJoker => "Space Joker",
+            Joker::OopsAllSixes => "Oops! All 6s",
+        }
+    }
+
+    // The suit a suit joker looks for
+    fn suit(self) -> Option<Suit> {
+        match self {
+            Joker::Greedy => Some(Suit::Diamonds),
+            Joker::Lusty => Some(Suit::Hearts),
+            Joker::Wrathful => Some(Suit::Spades),
+            Joker::Gluttonous => Some(Suit::Clubs),
+            _ => None,
+        }
+    }
+
+    // The poker hand a conditional joker looks for and what it gives
+    fn condition(self) -> Option<(PokerHand, Effect)> {
+        match self {
+            Joker::Jolly => Some((PokerHand::Pair, Effect::Mult(8.0))),
+            Joker::Zany => Some((PokerHand::ThreeOfAKind, Effect::Mult(12.0))),
+            Joker::Mad => Some((PokerHand::TwoPair, Effect::Mult(10.0))),
+            Joker::Crazy => Some((PokerHand::Straight, Effect::Mult(12.0))),
+            Joker::Droll => Some((PokerHand::Flush, Effect::Mult(10.0))),
+            Joker::Sly => Some((PokerHand::Pair, Effect::Chips(50.0))),
+            Joker::Wily => Some((PokerHand::ThreeOfAKind, Effect::Chips(100.0))),
+            Joker::Clever => Some((PokerHand::TwoPair, Effect::Chips(80.0))),
+            Joker::Devious => Some((PokerHand::Straight, Effect::Chips(100.0))),
+            Joker::Crafty => Some((PokerHand::Flush, Effect::Chips(80.0))),
+            Joker::TheDuo => Some((PokerHand::Pair, Effect::XMult(2.0))),
+            Joker::TheTrio => Some((PokerHand::ThreeOfAKind, Effect::XMult(3.0))),
+            Joker::TheFamily => Some((PokerHand::FourOfAKind, Effect::XMult(4.0))),
+            Joker::TheOrder => Some((PokerHand::Straight, Effect::XMult(3.0))),
+            Joker::TheTribe => Some((PokerHand::Flush, Effect::XMult(2.0))),
+            _ => None,
+        }
+    }
+}
+
+// Accepts the in game name or the variant name, ignoring case
+impl FromStr for Joker {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Joker, String> {
+        Joker::ALL
+            .into_iter()
+            .find(|joker| {
+                joker.to_str().eq_ignore_ascii_case(s)
+                    || format!("{:?}", joker).eq_ignore_ascii_case(s)
+            })
+            .ok_or_else(|| format!("Unknown joker: {}", s))
+    }
+}
+
+impl JokerEffect for Joker {
+    fn every_played_card_scores(&self) -> bool {
+        *self == Joker::Splash
+    }
+
+    fn four_card_hands(&self) -> bool {
+        *self == Joker::FourFingers
+    }
+
+    fn chance_multiplier(&self) -> u32 {
+        match self {
+            Joker::OopsAllSixes => 2,
+            _ => 1,
+        }
+    }
+
+    fn on_hand_evaluated(&self, ctx: &ScoringContext) -> Vec<Effect> {
+        if *self != Joker::SpaceJoker {
+            return Vec::new();
+        }
+        // The level up happens before the hand scores
+        let (chips, mult) = HandLevels::increment(ctx.hand);
+        ctx.chance
+            .roll(1, 4, vec![Effect::Chips(chips), Effect::Mult(mult)])
+    }
+
+    fn on_scored_card(&self, card: &Card, ctx: &ScoringContext) -> Vec<Effect> {
+        // Stone cards have no rank or suit
+        let stone = card.is_stone();
+        let retrigger = match self {
+            Joker::Hack => {
+                !stone && matches!(card.rank, Rank::Two | Rank::Three | Rank::Four | Rank::Five)
+            }
+            Joker::SockAndBuskin => !stone && card.rank.is_face(),
+            Joker::Dusk => ctx.round.final_hand,
+            Joker::HangingChad => {
+                // Compare by position so duplicate cards don't also trigger
+                if ctx.scored_index.get() == Some(0) {
+                    return vec![Effect::Retrigger(2)];
+                }
+                false
+            }
+            Joker::Bloodstone
+                if !stone && (card.suit == Suit::Hearts || card.suit == Suit::Wild) =>
+            {
+                return ctx.chance.roll(1, 2, vec![Effect::XMult(1.5)]);
+            }
+            _ => false,
+        };
+        if retrigger {
+            return vec![Effect::Retrigger(1)];
+        }
+        match self.suit() {
+            // Wild cards count as every suit
+            Some(suit) if !stone && (card.suit == suit || card.suit == Suit::Wild) => {
+                vec![Effect::Mult(3.0)]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn on_held_card(&self, card: &Card, _ctx: &ScoringContext) -> Vec<Effect> {
+        if card.is_stone() {
+            return Vec::new();
+        }
+        match (self, card.rank) {
+            (Joker::Baron, Rank::King) => vec![Effect::XMult(1.5)],
+            (Joker::ShootTheMoon, Rank::Queen) => vec![Effect::Mult(13.0)],
+            _ => Vec::new(),
+        }
+    }
+
+    fn on_end_of_hand(&self, ctx: &ScoringContext) -> Vec<Effect> {
+        if *self == Joker::Joker {
+            return vec![Effect::Mult(4.0)];
+        }
+        match self.condition() {
+            Some((hand, effect)) if ctx.contains(hand) => vec![effect],
+            _ => Vec::new(),
+        }
+    }
 }
@@ -1,186 +1,47 @@
-#![feature(stmt_expr_attributes)]
-use card::{Rank, Suit};
-use hand::PokerHand;
-use once_cell::sync::Lazy;
-use rayon::prelude::*;
-use serde::{Deserialize, Serialize};
-use serde_wasm_bindgen;
-use std::{collections::HashMap, sync::{Arc, Mutex}};
-use wasm_bindgen::{ prelude::*}; // For initializing statics
-
-
-// Enabling this should allow for parallelism in wasm
-// But for some reason the web workers won't work
-// so disabling for now
-// #[cfg(target_arch = "wasm32")]
-// pub use wasm_bindgen_rayon::init_thread_pool;
-
-mod card;
-mod deck;
-mod hand;
+//! Odds and scores for Balatro hands. The core types are plain Rust, and the
+//! `wasm` feature adds the bindings used by the web page on top.
+
+// The modules are internal, the public API is the items re-exported below
+pub(crate) mod card;
+pub(crate) mod deck;
+#[cfg(feature = "ffi")]
+mod ffi;
+pub(crate) mod hand;
+pub(crate) mod joker;
+#[cfg(feature = "python")]
+mod python;
+pub(crate) mod scoring;
+pub(crate) mod simulation;
 mod utils;
-use deck::Deck;
-
-// static mut DECK: Deck = Deck { cards: Vec::new() };
-static DECK: Lazy<Mutex<Deck>> = Lazy::new(|| Mutex::new(Deck::new()));
-
-#[wasm_bindgen]
-extern "C" {
-    #[wasm_bindgen(js_namespace = console)]
-    fn error(msg: &str);
-}
-
-#[wasm_bindgen]
-pub fn parse_deck(deck: JsValue) -> JsValue {
-    let deck = deck::Deck::from_jsvalue(deck);
-    match deck {
-        Ok(deck) => {
-            let mut _deck = DECK.lock().unwrap();
-            *_deck = deck;
-            drop(_deck);
-            JsValue::from_f64(0f64)
-        }
-        Err(e) => {
-            error(&e);
-            JsValue::from_f64(1f64)
-        }
-    }
-}
-
-#[wasm_bindgen]
-pub fn new_deck() -> JsValue {
-    let deck = deck::Deck::new();
-    deck.to_jsvalue()
-}
-
-#[wasm_bindgen]
-pub fn add_card(suit: String, rank: i32) -> Result<(), JsValue> {
-    match DECK.lock() {
-        Ok(mut d) => {
-            d.add_card(card::Card {
-                suit: Suit::from_str(suit.as_str()),
-                rank: Rank::from_int(rank as usize),
-            });
-        }
-        Err(e) => {
-            error(&format!("Failed to lock deck: {}", e));
-            return Err(JsValue::from_str("Failed to lock deck"));
-        }
-    }
-    Ok(())
-}
-#[wasm_bindgen]
-pub fn remove_card(suit: JsValue, rank: JsValue) -> Result<(), JsValue> {
-    let suit = card::Suit::from_jsvalue(suit);
-    let rank = card::Rank::from_jsvalue(rank);
-    match (suit, rank) {
-        (Ok(suit), Ok(rank)) => {
-            match DECK.lock() {
-                Ok(mut d) => {
-                    d.remove_card(&card::Card { suit, rank });
-                }
-                Err(e) => {
-                    error(&format!("Failed to lock deck: {}", e));
-                    return Err(JsValue::from_str("Failed to lock deck"));
-                }
-            }
-            Ok(())
-        }
-        _ => Err(JsValue::from_str("Invalid suit or rank")),
-    }
-}
-
-#[wasm_bindgen]
-pub fn show_deck() -> JsValue {
-    match DECK.lock() {
-        Ok(d) => d.to_jsvalue(),
-        Err(e) => {
-            error(&format!("Failed to lock deck: {}", e));
-            JsValue::NULL
-        }
-    }
-}
-
-#[allow(dead_code)]
-#[wasm_bindgen]
-#[derive(Serialize, Deserialize)]
-pub struct PokerHandResult {
-    hand: PokerHand,
-    freq: u32,
-    probability: f64,
-}
-
-#[allow(dead_code)]
-#[derive(Serialize, Deserialize)]
-#[wasm_bindgen]
-pub struct PokerHandResults {
-    results: Vec<PokerHandResult>,
-}
-
-#[wasm_bindgen]
-pub fn draw_trial(hand_size: u8, trials: u32) -> JsValue {
-    let deck = match DECK.lock() {
-        Ok(d) => d,
-        Err(e) => {
-            error(&format!("Failed to lock deck: {}", e));
-            return JsValue::NULL;
-        }
-    };
-    let deck_clone = Arc::new(deck.clone());
-    drop(deck);
-    // Use a parallel iterator to perform the trials in parallel
-    let net_result: HashMap<PokerHand, u32> = (0..trials)
-        .into_par_iter()
-        .map(|_| {
-            let mut hand = deck_clone.draw_hand(hand_size);
-            hand.evaluate_poker_hands()
-        })
-        .reduce(
-            || HashMap::new(),
-            |mut acc, res| {
-                // Combine results from each trial
-                for (&k, &v) in res.iter() {
-                    *acc.entry(k).or_insert(0) += v;
-                }
-                acc
-            },
-        );
-
-    let net_result: HashMap<PokerHand, f64> = net_result
-        .iter()
-        .map(|(k, v)| (*k, *v as f64 / trials as f64))
-        .collect();
-
-    serde_wasm_bindgen::to_value(&net_result).unwrap()
-    // let net_result = (0..trials)
-    //     .into_par_iter()
-    //     .map(|_| {
-    //         let mut hand = deck_clone.draw_hand(hand_size);
-    //         let result: HashMap<PokerHand, u32> = hand.evaluate_poker_hands();
-    //         result
-    //     })
-    //     .reduce(
-    //         || HashMap::new(),
-    //         |mut acc, x| {
-    //             for (&k, &v) in x.iter() {
-    //                 let count = acc.entry(k).or_insert(0);
-    //                 *count += v;
-    //             }
-    //             acc
-    //         },
-    //     );
-    // let mut net_result = HashMap::new();
-    // for _ in 0..trials {
-    //     let mut hand = deck_clone.draw_hand(hand_size);
-    //     let result: HashMap<PokerHand, u32> = hand.evaluate_poker_hands();
-    //     for (&k, &v) in result.iter() {
-    //         let count = net_result.entry(k).or_insert(0);
-    //         *count += v;
-    //     }
-    // }
-    // let net_result: HashMap<PokerHand, f64> = net_result
-    //     .iter()
-    //     .map(|(k, v)| (*k, *v as f64 / trials as f64))
-    //     .collect();
-    // serde_wasm_bindgen::to_value(&net_result).unwrap()
-}
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+mod wasm;
+
+pub use card::{Card, Enhancement, Rank, Seal, Suit};
+pub use deck::{Deck, DeckOp, DeckPreset};
+pub use hand::{Hand, Play, PokerHand};
+pub use joker::Joker;
+pub use scoring::levels::{HandLevel, HandLevels, LevelUpgrade, Planet};
+pub use scoring::{
+    choose_play, held_cards, play_cards, score_play, BigNum, Chance, Effect, JokerEffect, Rolls,
+    RoundState, Score, ScoringContext,
+};
+pub use simulation::discard::{advise_discards, DiscardOdds};
+pub use simulation::distribution::{
+    HistogramBin, Percentile, ScoreDistribution, DEFAULT_PERCENTILES,
+};
+pub use simulation::exact::{distinct_draws, exact_odds, exact_odds_given, EXACT_LIMIT};
+pub use simulation::progress::{Simulation, SimulationSnapshot};
+pub use simulation::results::{
+    draw_trial_results, ConfidenceInterval, PokerHandResult, PokerHandResults, RESULTS_VERSION,
+};
+pub use simulation::round::{simulate_round, PlayOdds, PlayPolicy, RoundOdds};
+pub use simulation::search::{search_improvements, Improvement, OpBudget};
+pub use simulation::{
+    at_least, best_hand_trials, compare_decks, draw_trials, draw_trials_given, score_trials,
+    sweep_hand_sizes, BestHandOdds, DeckComparison, HandSizeOdds, ScoreTrials, Threads,
+};
+
+// Starts the web workers directly, prefer `initThreads` from JS which also
+// lets `draw_trial` know the workers are there
+#[cfg(all(target_arch = "wasm32", feature = "threads"))]
+pub use wasm_bindgen_rayon::init_thread_pool;
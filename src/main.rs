@@ -1,58 +1,281 @@
-#![feature(stmt_expr_attributes)]
-mod card;
-mod deck;
-mod hand;
-mod joker;
-mod utils;
-
 use std::collections::HashMap;
 use std::env;
+use std::io::{self, IsTerminal, Write};
+use std::process;
 
-use card::{Card, Rank, Suit};
-use deck::Deck;
-use rayon::prelude::*;
-use rayon::ThreadPoolBuilder;
+use balatro_handy_odds_calculator::{
+    score_trials, sweep_hand_sizes, Deck, DeckPreset, HandLevels, Joker, PokerHand, RoundState,
+    Simulation, Threads, DEFAULT_PERCENTILES,
+};
 
-use crate::hand::PokerHand;
+// Trials run between redraws of the progress bar
+const PROGRESS_CHUNK: u32 = 2000;
+const PROGRESS_WIDTH: usize = 30;
 
-fn main() {
-    let mut deck = Deck::new();
+const USAGE: &str = "Usage: balatro-calculator [trials] [--deck <name>] [--seed <seed>] \
+                     [--hand-size <size>] [--sweep <min>-<max>] \
+                     [--score [--jokers <name>,<name>...] [--levels <json>] [--final-hand]]";
 
-    let mut results: HashMap<PokerHand, f64> = HashMap::new();
+struct Args {
+    trials: u32,
+    preset: DeckPreset,
+    hand_size: u8,
+    // Range of hand sizes to print as a matrix instead of a single hand size
+    sweep: Option<(u8, u8)>,
+    // Print the score distribution instead of the odds
+    score: bool,
+    jokers: Vec<Joker>,
+    // Hand levels for scoring, e.g. {"Flush": 3} for a level 3 Flush
+    levels: HandLevels,
+    // Score as the last hand of the round, for jokers like Dusk
+    final_hand: bool,
+    seed: Option<u64>,
+}
+
+// Every hand not in the JSON object stays at level 1
+fn parse_levels(json: &str) -> Result<HandLevels, String> {
+    let parsed: HashMap<PokerHand, u32> =
+        serde_json::from_str(json).map_err(|e| format!("Invalid levels: {}", e))?;
+    let mut levels = HandLevels::default();
+    for (hand, level) in parsed {
+        if level == 0 {
+            return Err(format!("Invalid level for {}: 0", hand.to_str()));
+        }
+        levels.level_up(hand, level - 1);
+    }
+    Ok(levels)
+}
 
-    let trials = env::args()
-        .nth(1)
-        .unwrap_or("10000".to_string())
-        .parse::<u64>()
-        .unwrap_or(10000);
+fn parse_args() -> Result<Args, String> {
+    let mut trials = None;
+    let mut deck_name = "Standard".to_string();
+    let mut seed = None;
+    let mut hand_size = None;
+    let mut sweep = None;
+    let mut score = false;
+    let mut jokers = Vec::new();
+    let mut levels = HandLevels::default();
+    let mut final_hand = false;
 
-    let net_result: HashMap<PokerHand, u32> = (0..trials)
-        .into_par_iter()
-        .map(|_| {
-            let mut hand = deck.draw_hand(5);
-            hand.evaluate_poker_hands()
-        })
-        .reduce(
-            || HashMap::new(),
-            |mut acc, res| {
-                // Combine results from each trial
-                for (&k, &v) in res.iter() {
-                    *acc.entry(k).or_insert(0) += v;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--deck" => {
+                deck_name = args.next().ok_or("Missing value for --deck")?;
+            }
+            "--seed" => {
+                let value = args.next().ok_or("Missing value for --seed")?;
+                seed = Some(value.parse::<u64>().map_err(|_| "Invalid seed")?);
+            }
+            "--hand-size" => {
+                let value = args.next().ok_or("Missing value for --hand-size")?;
+                hand_size = Some(value.parse::<u8>().map_err(|_| "Invalid hand size")?);
+            }
+            "--sweep" => {
+                let value = args.next().ok_or("Missing value for --sweep")?;
+                let (min, max) = value
+                    .split_once('-')
+                    .ok_or("Expected --sweep <min>-<max>")?;
+                let min = min.parse::<u8>().map_err(|_| "Invalid sweep range")?;
+                let max = max.parse::<u8>().map_err(|_| "Invalid sweep range")?;
+                if min > max {
+                    return Err("Invalid sweep range".to_string());
                 }
-                acc
-            },
+                sweep = Some((min, max));
+            }
+            "--score" => score = true,
+            "--jokers" => {
+                let value = args.next().ok_or("Missing value for --jokers")?;
+                jokers = value
+                    .split(',')
+                    .map(|name| name.trim().parse::<Joker>())
+                    .collect::<Result<Vec<Joker>, String>>()?;
+            }
+            "--levels" => {
+                let value = args.next().ok_or("Missing value for --levels")?;
+                levels = parse_levels(&value)?;
+            }
+            "--final-hand" => final_hand = true,
+            "--help" | "-h" => return Err(USAGE.to_string()),
+            // The only positional argument is the number of trials
+            _ if trials.is_none() && !arg.starts_with('-') => {
+                trials = Some(
+                    arg.parse::<u32>()
+                        .map_err(|_| format!("Invalid trials: {}\n{}", arg, USAGE))?,
+                );
+            }
+            _ => return Err(format!("Unknown argument: {}\n{}", arg, USAGE)),
+        }
+    }
+
+    let preset = DeckPreset::from_str(deck_name.as_str(), seed)?;
+    Ok(Args {
+        trials: trials.unwrap_or(10000),
+        preset,
+        hand_size: hand_size.unwrap_or(5 + preset.hand_size_bonus()),
+        sweep,
+        score,
+        jokers,
+        levels,
+        final_hand,
+        seed,
+    })
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    };
+    let deck = Deck::from_preset(args.preset);
+    let trials = args.trials;
+    // The sweep checks its own range
+    if args.sweep.is_none() {
+        if let Err(e) = deck.check_hand_size(args.hand_size) {
+            eprintln!("Invalid hand size: {}\n{}", e, USAGE);
+            process::exit(1);
+        }
+    }
+
+    if let Some((min, max)) = args.sweep {
+        if let Err(e) = print_sweep(&deck, &args, min, max) {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+        return;
+    }
+    if args.score {
+        print_scores(&deck, &args);
+        return;
+    }
+
+    let seed = args.seed.unwrap_or_else(rand::random);
+    let mut sim = Simulation::new(deck, args.hand_size, trials, seed);
+    // Only draw the bar for a person watching, not when stderr is redirected
+    let show_progress = io::stderr().is_terminal();
+    while !sim.is_done() {
+        sim.step(PROGRESS_CHUNK, Threads::Pool);
+        if show_progress {
+            draw_progress(&sim, trials);
+        }
+    }
+    if show_progress {
+        eprint!("\r{}\r", " ".repeat(PROGRESS_WIDTH + 30));
+    }
+
+    println!(
+        "Deck: {}, Hand size: {}, Trials: {}",
+        args.preset.to_str(),
+        args.hand_size,
+        trials
+    );
+    for result in sim.snapshot().results.results.iter() {
+        println!(
+            "Hand: {:?}, Probability: {}",
+            result.hand, result.probability
         );
+    }
+}
 
-    let results: HashMap<PokerHand, f64> = net_result
-        .iter()
-        .map(|(k, v)| (*k, *v as f64 / trials as f64))
+// Redraws the progress bar in place, e.g. [######      ]  50% 5000/10000
+fn draw_progress(sim: &Simulation, trials: u32) {
+    let filled = (sim.progress() * PROGRESS_WIDTH as f64).round() as usize;
+    eprint!(
+        "\r[{}{}] {:>3.0}% {}/{}",
+        "#".repeat(filled),
+        " ".repeat(PROGRESS_WIDTH - filled),
+        sim.progress() * 100.0,
+        sim.completed(),
+        trials
+    );
+    let _ = io::stderr().flush();
+}
+
+fn print_sweep(deck: &Deck, args: &Args, min: u8, max: u8) -> Result<(), String> {
+    let sweep = sweep_hand_sizes(deck, min, max, args.trials, Threads::Pool)?;
+    // Only show the hands that showed up for at least one hand size
+    let hands: Vec<PokerHand> = PokerHand::ALL
+        .into_iter()
+        .filter(|hand| {
+            sweep
+                .iter()
+                .any(|odds| odds.odds.get(hand).is_some_and(|&p| p > 0.0))
+        })
         .collect();
 
-    for (k, v) in results.iter() {
+    println!("Deck: {}, Trials: {}", args.preset.to_str(), args.trials);
+    print!("{:>4}", "Size");
+    for hand in hands.iter() {
+        print!(" {:>14}", format!("{:?}", hand));
+    }
+    println!();
+    for odds in sweep.iter() {
+        print!("{:>4}", odds.hand_size);
+        for hand in hands.iter() {
+            print!(" {:>14.4}", odds.odds.get(hand).unwrap_or(&0.0));
+        }
+        println!();
+    }
+    Ok(())
+}
+
+fn print_scores(deck: &Deck, args: &Args) {
+    let seed = args.seed.unwrap_or_else(rand::random);
+    let scores = score_trials(
+        deck,
+        args.hand_size,
+        &args.jokers,
+        &RoundState {
+            final_hand: args.final_hand,
+            levels: args.levels.clone(),
+        },
+        args.trials,
+        seed,
+        &DEFAULT_PERCENTILES,
+        Threads::Pool,
+    );
+    let jokers: Vec<&str> = args.jokers.iter().map(|joker| joker.to_str()).collect();
+
+    println!(
+        "Deck: {}, Hand size: {}, Trials: {}, Jokers: [{}]{}",
+        args.preset.to_str(),
+        args.hand_size,
+        args.trials,
+        jokers.join(", "),
+        if args.final_hand { ", Final hand" } else { "" }
+    );
+    print!(
+        "{:>14} {:>7} {:>10} {:>10}",
+        "Hand", "Trials", "Min", "Mean"
+    );
+    for percentile in DEFAULT_PERCENTILES.iter() {
+        print!(" {:>10}", format!("p{}", percentile));
+    }
+    println!(" {:>10}  Histogram (log)", "Max");
+
+    let mut rows = vec![("All".to_string(), &scores.overall)];
+    for hand in PokerHand::ALL.iter() {
+        if let Some(distribution) = scores.by_hand.get(hand) {
+            rows.push((format!("{:?}", hand), distribution));
+        }
+    }
+    for (name, distribution) in rows {
+        print!(
+            "{:>14} {:>7} {:>10} {:>10}",
+            name, distribution.trials, distribution.min, distribution.mean
+        );
+        for percentile in distribution.percentiles.iter() {
+            print!(" {:>10}", percentile.score);
+        }
+        println!(" {:>10}  {}", distribution.max, distribution.sparkline());
+    }
+    if scores.glass_destroyed > 0.0 {
         println!(
-            "Hand: {:?}, Probability: {}",
-            k,
-            *v,
+            "Glass cards destroyed per hand: {:.3}",
+            scores.glass_destroyed
         );
     }
 }
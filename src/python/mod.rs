@@ -0,0 +1,389 @@
+// Python bindings, built with maturin, see "Python" in the README.
+// Poker hands are passed around by their serialized names, e.g. "FullHouse".
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::card::{Card, Enhancement, Rank, Seal, Suit};
+use crate::deck::{Deck, DeckPreset};
+use crate::hand::{Hand, PokerHand};
+use crate::simulation::results::{draw_trial_results, PokerHandResults};
+use crate::simulation::{self, Threads};
+
+#[pyclass(name = "Card", module = "balatro_odds", frozen, eq, hash)]
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct PyCard {
+    card: Card,
+}
+
+#[pymethods]
+impl PyCard {
+    // Ranks are 2 to 14 with Ace as 14, like the JS objects
+    #[new]
+    #[pyo3(signature = (rank, suit, seal=None, enhancement=None))]
+    fn new(
+        rank: usize,
+        suit: &str,
+        seal: Option<&str>,
+        enhancement: Option<&str>,
+    ) -> PyResult<PyCard> {
+        let rank = Rank::from_int(rank);
+        if rank == Rank::NONE {
+            return Err(PyValueError::new_err("Invalid rank"));
+        }
+        let suit = Suit::from(suit);
+        if suit == Suit::NONE {
+            return Err(PyValueError::new_err("Invalid suit"));
+        }
+        let seal = seal.map(str::parse::<Seal>).transpose();
+        let enhancement = enhancement.map(str::parse::<Enhancement>).transpose();
+        Ok(PyCard {
+            card: Card {
+                rank,
+                suit,
+                seal: seal.map_err(PyValueError::new_err)?,
+                enhancement: enhancement.map_err(PyValueError::new_err)?,
+            },
+        })
+    }
+
+    #[getter]
+    fn rank(&self) -> usize {
+        self.card.rank.to_int()
+    }
+
+    #[getter]
+    fn suit(&self) -> &'static str {
+        self.card.suit.to_str()
+    }
+
+    #[getter]
+    fn seal(&self) -> Option<&'static str> {
+        self.card.seal.map(Seal::to_str)
+    }
+
+    #[getter]
+    fn enhancement(&self) -> Option<&'static str> {
+        self.card.enhancement.map(Enhancement::to_str)
+    }
+
+    fn __repr__(&self) -> String {
+        let mut repr = format!("Card({}, '{}'", self.rank(), self.suit());
+        if let Some(seal) = self.seal() {
+            repr += &format!(", seal='{}'", seal);
+        }
+        if let Some(enhancement) = self.enhancement() {
+            repr += &format!(", enhancement='{}'", enhancement);
+        }
+        repr + ")"
+    }
+
+    fn __str__(&self) -> String {
+        self.card.to_str()
+    }
+}
+
+fn to_py_cards(cards: &[Card]) -> Vec<PyCard> {
+    cards.iter().map(|&card| PyCard { card }).collect()
+}
+
+fn from_py_cards(cards: Vec<PyCard>) -> Vec<Card> {
+    cards.into_iter().map(|card| card.card).collect()
+}
+
+#[pyclass(name = "Hand", module = "balatro_odds")]
+#[derive(Clone)]
+struct PyHand {
+    hand: Hand,
+}
+
+#[pymethods]
+impl PyHand {
+    #[new]
+    fn new(cards: Vec<PyCard>) -> PyHand {
+        PyHand {
+            hand: Hand::from(from_py_cards(cards)),
+        }
+    }
+
+    #[getter]
+    fn cards(&self) -> Vec<PyCard> {
+        to_py_cards(&self.hand.cards)
+    }
+
+    // Every poker hand the cards contain, e.g. {"Pair": 1}, the same hands
+    // best_hand picks from
+    fn evaluate_poker_hands<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        for (hand, count) in self.hand.contained_hands() {
+            dict.set_item(hand.to_str(), count)?;
+        }
+        Ok(dict)
+    }
+
+    fn best_hand(&self) -> &'static str {
+        self.hand.best_hand().to_str()
+    }
+
+    fn __len__(&self) -> usize {
+        self.hand.cards.len()
+    }
+
+    fn __repr__(&self) -> String {
+        let cards: Vec<String> = self.cards().iter().map(PyCard::__repr__).collect();
+        format!("Hand([{}])", cards.join(", "))
+    }
+}
+
+#[pyclass(name = "Deck", module = "balatro_odds")]
+#[derive(Clone)]
+struct PyDeck {
+    deck: Deck,
+}
+
+#[pymethods]
+impl PyDeck {
+    // A standard deck, or one of the decks in game by name, e.g. "Abandoned".
+    // The seed is only used by the Erratic deck.
+    #[new]
+    #[pyo3(signature = (preset=None, seed=None))]
+    fn new(preset: Option<&str>, seed: Option<u64>) -> PyResult<PyDeck> {
+        let deck = match preset {
+            Some(name) => {
+                Deck::from_preset(DeckPreset::from_str(name, seed).map_err(PyValueError::new_err)?)
+            }
+            None => Deck::new(),
+        };
+        Ok(PyDeck { deck })
+    }
+
+    #[getter]
+    fn cards(&self) -> Vec<PyCard> {
+        to_py_cards(self.deck.cards())
+    }
+
+    fn add_card(&mut self, card: PyCard) {
+        self.deck.add_card(card.card);
+    }
+
+    // A card without a seal or enhancement removes any card of its rank and
+    // suit, raises ValueError when there is none
+    fn remove_card(&mut self, card: PyCard) -> PyResult<()> {
+        if !self.deck.remove_card(&card.card) {
+            return Err(PyValueError::new_err("Card is not in the deck"));
+        }
+        Ok(())
+    }
+
+    #[pyo3(signature = (size, seed=None))]
+    fn draw_hand(&self, size: u8, seed: Option<u64>) -> PyResult<PyHand> {
+        self.deck
+            .check_hand_size(size)
+            .map_err(PyValueError::new_err)?;
+        let hand = match seed {
+            Some(seed) => self.deck.draw_hand_seeded(size, seed),
+            None => self.deck.draw_hand(size),
+        };
+        Ok(PyHand { hand })
+    }
+
+    // Odds of every poker hand, in the same shape as the JSON results
+    #[pyo3(signature = (hand_size, trials, seed=None))]
+    fn draw_trial<'py>(
+        &self,
+        py: Python<'py>,
+        hand_size: u8,
+        trials: u32,
+        seed: Option<u64>,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        self.deck
+            .check_hand_size(hand_size)
+            .map_err(PyValueError::new_err)?;
+        let seed = seed.unwrap_or_else(rand::random);
+        let results = py.allow_threads(|| {
+            draw_trial_results(&self.deck, hand_size, trials, seed, Threads::Pool)
+        });
+        results_dict(py, &results)
+    }
+
+    // Odds of each poker hand being the best one, and of the best one ranking
+    // at least as high as each poker hand
+    fn draw_trial_best<'py>(
+        &self,
+        py: Python<'py>,
+        hand_size: u8,
+        trials: u32,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let odds = py
+            .allow_threads(|| {
+                simulation::best_hand_trials(&self.deck, hand_size, trials, Threads::Pool)
+            })
+            .map_err(PyValueError::new_err)?;
+        let dict = PyDict::new(py);
+        dict.set_item("best", odds_dict(py, odds.best)?)?;
+        dict.set_item("at_least", odds_dict(py, odds.at_least)?)?;
+        Ok(dict)
+    }
+
+    // Odds when the held cards are kept and `to_draw` more are drawn, the held
+    // cards are taken out of the deck first
+    fn draw_trial_given<'py>(
+        &self,
+        py: Python<'py>,
+        held: PyHand,
+        to_draw: u8,
+        trials: u32,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let deck = self.without(&held.hand)?;
+        let odds = py
+            .allow_threads(|| {
+                simulation::draw_trials_given(&held.hand, &deck, to_draw, trials, Threads::Pool)
+            })
+            .map_err(PyValueError::new_err)?;
+        odds_dict(py, odds)
+    }
+
+    // Exact odds of every poker hand, going through every possible hand.
+    // Raises ValueError when there are too many, e.g. 8 cards from a full deck.
+    fn exact_odds<'py>(&self, py: Python<'py>, hand_size: u8) -> PyResult<Bound<'py, PyDict>> {
+        let odds = py
+            .allow_threads(|| simulation::exact::exact_odds(&self.deck, hand_size, Threads::Pool))
+            .map_err(PyValueError::new_err)?;
+        odds_dict(py, odds)
+    }
+
+    // Exact odds when the held cards are kept and `to_draw` more are drawn,
+    // the held cards are taken out of the deck first
+    fn exact_odds_given<'py>(
+        &self,
+        py: Python<'py>,
+        held: PyHand,
+        to_draw: u8,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let deck = self.without(&held.hand)?;
+        let odds = py
+            .allow_threads(|| {
+                simulation::exact::exact_odds_given(&held.hand, &deck, to_draw, Threads::Pool)
+            })
+            .map_err(PyValueError::new_err)?;
+        odds_dict(py, odds)
+    }
+
+    // Every way to discard up to `max_discards` cards from the hand, best odds
+    // of the target first, as dicts like {"discard": [Card], "odds": 0.19,
+    // "exact": True}. The hand is taken out of the deck first, and discards
+    // with more possible draws than `trials` are sampled.
+    #[pyo3(signature = (hand, target, max_discards=5, trials=10_000))]
+    fn advise_discards<'py>(
+        &self,
+        py: Python<'py>,
+        hand: PyHand,
+        target: &str,
+        max_discards: u8,
+        trials: u32,
+    ) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        let target = poker_hand(target)?;
+        let deck = self.without(&hand.hand)?;
+        let advice = py
+            .allow_threads(|| {
+                simulation::discard::advise_discards(
+                    &hand.hand,
+                    &deck,
+                    target,
+                    max_discards,
+                    trials,
+                    Threads::Pool,
+                )
+            })
+            .map_err(PyValueError::new_err)?;
+        advice
+            .iter()
+            .map(|option| {
+                let dict = PyDict::new(py);
+                dict.set_item("discard", to_py_cards(&option.discard))?;
+                dict.set_item("odds", option.odds)?;
+                dict.set_item("exact", option.exact)?;
+                Ok(dict)
+            })
+            .collect()
+    }
+
+    fn __len__(&self) -> usize {
+        self.deck.size()
+    }
+}
+
+impl PyDeck {
+    // The deck without the cards of the hand, which must all be in it
+    fn without(&self, hand: &Hand) -> PyResult<Deck> {
+        let mut deck = self.deck.clone();
+        for card in hand.cards.iter() {
+            if !deck.remove_card(card) {
+                return Err(PyValueError::new_err(format!(
+                    "Held card {} is not in the deck",
+                    card.to_str()
+                )));
+            }
+        }
+        Ok(deck)
+    }
+}
+
+fn poker_hand(name: &str) -> PyResult<PokerHand> {
+    PokerHand::ALL
+        .into_iter()
+        .find(|hand| hand.to_str() == name)
+        .ok_or_else(|| PyValueError::new_err(format!("Unknown poker hand {}", name)))
+}
+
+fn odds_dict<'py, I>(py: Python<'py>, odds: I) -> PyResult<Bound<'py, PyDict>>
+where
+    I: IntoIterator<Item = (PokerHand, f64)>,
+{
+    let dict = PyDict::new(py);
+    for (hand, odds) in odds {
+        dict.set_item(hand.to_str(), odds)?;
+    }
+    Ok(dict)
+}
+
+fn results_dict<'py>(py: Python<'py>, results: &PokerHandResults) -> PyResult<Bound<'py, PyDict>> {
+    let rows = results
+        .results
+        .iter()
+        .map(|result| {
+            let row = PyDict::new(py);
+            row.set_item("hand", result.hand.to_str())?;
+            row.set_item("count", result.count)?;
+            row.set_item("probability", result.probability)?;
+            let ci = PyDict::new(py);
+            ci.set_item("low", result.ci.low)?;
+            ci.set_item("high", result.ci.high)?;
+            row.set_item("ci", ci)?;
+            Ok(row)
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    let dict = PyDict::new(py);
+    dict.set_item("version", results.version)?;
+    dict.set_item("hand_size", results.hand_size)?;
+    dict.set_item("trials", results.trials)?;
+    dict.set_item("seed", results.seed)?;
+    dict.set_item("confidence", results.confidence)?;
+    dict.set_item("elapsed_ms", results.elapsed_ms)?;
+    dict.set_item("results", rows)?;
+    Ok(dict)
+}
+
+#[pymodule]
+#[pyo3(name = "balatro_odds")]
+fn python_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyCard>()?;
+    m.add_class::<PyHand>()?;
+    m.add_class::<PyDeck>()?;
+    // Every poker hand from the lowest to the highest ranked
+    m.add(
+        "POKER_HANDS",
+        PokerHand::ALL.map(PokerHand::to_str).to_vec(),
+    )?;
+    Ok(())
+}
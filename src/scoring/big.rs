@@ -0,0 +1,277 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign};
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// Balatro switches to e-notation from this many digits
+const NOTATION_DIGITS: f64 = 11.0;
+
+/// A number for scores that can grow past f64, stored as mantissa * 2^exponent
+/// with the mantissa between 1 and 2. Scaling by powers of two is exact, so
+/// while a score fits in an f64 the arithmetic gives exactly the f64 result.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BigNum {
+    mantissa: f64,
+    exponent: i64,
+}
+
+impl BigNum {
+    pub const ZERO: BigNum = BigNum {
+        mantissa: 0.0,
+        exponent: 0,
+    };
+
+    fn normalized(mut mantissa: f64, mut exponent: i64) -> BigNum {
+        if mantissa == 0.0 || !mantissa.is_finite() {
+            return BigNum {
+                mantissa,
+                exponent: if mantissa == 0.0 { 0 } else { exponent },
+            };
+        }
+        // Powers of two past the f64 range would scale the mantissa to 0 or inf
+        let shift = (mantissa.abs().log2().floor() as i32).clamp(-1074, 1023);
+        mantissa /= 2f64.powi(shift);
+        exponent += shift as i64;
+        // log2 can be off by one close to a power of two
+        while mantissa.abs() >= 2.0 {
+            mantissa /= 2.0;
+            exponent += 1;
+        }
+        while mantissa.abs() < 1.0 {
+            mantissa *= 2.0;
+            exponent -= 1;
+        }
+        BigNum { mantissa, exponent }
+    }
+
+    // 10^power, for bounds on a log scale
+    pub fn pow10(power: f64) -> BigNum {
+        let log2 = power * std::f64::consts::LOG2_10;
+        let exponent = log2.floor();
+        BigNum::normalized(2f64.powf(log2 - exponent), exponent as i64)
+    }
+
+    // Infinite when the number doesn't fit in an f64
+    pub fn to_f64(self) -> f64 {
+        if self.exponent > f64::MAX_EXP as i64 {
+            return self.mantissa * f64::INFINITY;
+        }
+        if self.exponent < f64::MIN_EXP as i64 - 64 {
+            return 0.0;
+        }
+        // Split the scaling so subnormal results don't underflow early
+        let half = self.exponent / 2;
+        self.mantissa * 2f64.powi(half as i32) * 2f64.powi((self.exponent - half) as i32)
+    }
+
+    // Of the magnitude, so it works for negative numbers too
+    pub fn log10(self) -> f64 {
+        self.mantissa.abs().log10() + self.exponent as f64 * std::f64::consts::LOG10_2
+    }
+
+    pub fn total_cmp(&self, other: &BigNum) -> Ordering {
+        let sign = |x: &BigNum| x.mantissa.partial_cmp(&0.0).unwrap_or(Ordering::Equal);
+        match sign(self).cmp(&sign(other)) {
+            Ordering::Equal => {}
+            ordering => return ordering,
+        }
+        let magnitude = match self.exponent.cmp(&other.exponent) {
+            Ordering::Equal => self.mantissa.abs().total_cmp(&other.mantissa.abs()),
+            ordering => ordering,
+        };
+        match sign(self) {
+            Ordering::Less => magnitude.reverse(),
+            Ordering::Equal => Ordering::Equal,
+            Ordering::Greater => magnitude,
+        }
+    }
+
+    // Decimal mantissa and exponent, e.g. (1.5, 400) for 1.5e400
+    fn to_decimal(self) -> (f64, i64) {
+        let log10 = self.log10();
+        let mut exponent = log10.floor();
+        let mut mantissa = 10f64.powf(log10 - exponent);
+        if mantissa >= 10.0 {
+            mantissa /= 10.0;
+            exponent += 1.0;
+        }
+        (mantissa.copysign(self.mantissa), exponent as i64)
+    }
+
+    // Keeps all the precision, unlike `Display`. Numbers past f64 are written
+    // as the binary mantissa and exponent, e.g. "1.5*2^1400", since going
+    // through a decimal exponent would round them.
+    pub fn to_exact_string(self) -> String {
+        let value = self.to_f64();
+        // Subnormal f64s have lost some of the mantissa
+        if !value.is_normal() && self.mantissa != 0.0 {
+            format!("{}*2^{}", self.mantissa, self.exponent)
+        } else if value.abs() < 1e16 {
+            format!("{}", value)
+        } else {
+            format!("{:e}", value)
+        }
+    }
+
+    // Parses plain numbers, e-notation and the binary form of
+    // `to_exact_string`, including exponents past f64
+    pub fn parse(s: &str) -> Result<BigNum, String> {
+        let s = s.trim();
+        if let Ok(value) = s.parse::<f64>() {
+            if value.is_finite() {
+                return Ok(BigNum::from(value));
+            }
+        }
+        let invalid = || format!("Invalid number: {}", s);
+        if let Some((mantissa, exponent)) = s.split_once("*2^") {
+            let mantissa = mantissa.parse::<f64>().map_err(|_| invalid())?;
+            let exponent = exponent.parse::<i64>().map_err(|_| invalid())?;
+            if !mantissa.is_finite() {
+                return Err(invalid());
+            }
+            return Ok(BigNum::normalized(mantissa, exponent));
+        }
+        let (mantissa, exponent) = s.split_once(['e', 'E']).ok_or_else(invalid)?;
+        let mantissa = mantissa.parse::<f64>().map_err(|_| invalid())?;
+        let exponent = exponent.parse::<i64>().map_err(|_| invalid())?;
+        Ok(BigNum::from(mantissa) * BigNum::pow10(exponent as f64))
+    }
+}
+
+impl From<f64> for BigNum {
+    fn from(value: f64) -> BigNum {
+        BigNum::normalized(value, 0)
+    }
+}
+
+impl PartialEq<f64> for BigNum {
+    fn eq(&self, other: &f64) -> bool {
+        *self == BigNum::from(*other)
+    }
+}
+
+impl PartialOrd for BigNum {
+    fn partial_cmp(&self, other: &BigNum) -> Option<Ordering> {
+        Some(self.total_cmp(other))
+    }
+}
+
+impl Add for BigNum {
+    type Output = BigNum;
+
+    fn add(self, other: BigNum) -> BigNum {
+        // Zero has exponent 0, which can be bigger than a small number's
+        if self.mantissa == 0.0 {
+            return other;
+        }
+        if other.mantissa == 0.0 {
+            return self;
+        }
+        let (big, small) = if self.exponent >= other.exponent {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        // Too small to change the mantissa of the bigger number
+        let diff = big.exponent - small.exponent;
+        if diff > 64 {
+            return big;
+        }
+        BigNum::normalized(
+            big.mantissa + small.mantissa / 2f64.powi(diff as i32),
+            big.exponent,
+        )
+    }
+}
+
+impl AddAssign for BigNum {
+    fn add_assign(&mut self, other: BigNum) {
+        *self = *self + other;
+    }
+}
+
+impl Mul for BigNum {
+    type Output = BigNum;
+
+    fn mul(self, other: BigNum) -> BigNum {
+        BigNum::normalized(
+            self.mantissa * other.mantissa,
+            self.exponent + other.exponent,
+        )
+    }
+}
+
+impl MulAssign for BigNum {
+    fn mul_assign(&mut self, other: BigNum) {
+        *self = *self * other;
+    }
+}
+
+impl Div<f64> for BigNum {
+    type Output = BigNum;
+
+    fn div(self, other: f64) -> BigNum {
+        let other = BigNum::from(other);
+        BigNum::normalized(
+            self.mantissa / other.mantissa,
+            self.exponent - other.exponent,
+        )
+    }
+}
+
+// Rounded like the game shows scores, e.g. 1234 or 1.235e15
+impl fmt::Display for BigNum {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.mantissa == 0.0 || self.log10() < NOTATION_DIGITS {
+            return f.pad(&format!("{:.0}", self.to_f64()));
+        }
+        let (mut mantissa, mut exponent) = self.to_decimal();
+        // Rounding to 3 decimals can carry into the exponent
+        if (mantissa.abs() * 1000.0).round() >= 10000.0 {
+            mantissa /= 10.0;
+            exponent += 1;
+        }
+        f.pad(&format!("{:.3}e{}", mantissa, exponent))
+    }
+}
+
+impl Serialize for BigNum {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_exact_string())
+    }
+}
+
+struct BigNumVisitor;
+
+impl Visitor<'_> for BigNumVisitor {
+    type Value = BigNum;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a number or a string like \"1.5e400\" or \"1.5*2^1400\"")
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<BigNum, E> {
+        Ok(BigNum::from(value))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<BigNum, E> {
+        Ok(BigNum::from(value as f64))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<BigNum, E> {
+        Ok(BigNum::from(value as f64))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<BigNum, E> {
+        BigNum::parse(value).map_err(E::custom)
+    }
+}
+
+// Accepts plain numbers too, so older saved scores still load
+impl<'de> Deserialize<'de> for BigNum {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<BigNum, D::Error> {
+        deserializer.deserialize_any(BigNumVisitor)
+    }
+}
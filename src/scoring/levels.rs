@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::hand::PokerHand;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct HandLevel {
+    pub level: u32,
+    pub chips: f64,
+    pub mult: f64,
+}
+
+/// The level of every poker hand. Royal Flush is a Straight Flush in game,
+/// so the two share a level, and a play without a hand scores as High Card.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HandLevels {
+    levels: HashMap<PokerHand, HandLevel>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Planet {
+    Pluto,
+    Mercury,
+    Uranus,
+    Venus,
+    Saturn,
+    Jupiter,
+    Earth,
+    Mars,
+    Neptune,
+    PlanetX,
+    Ceres,
+    Eris,
+}
+
+/// A change to the hand levels.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(tag = "kind")]
+pub enum LevelUpgrade {
+    // Levels up the planet's hand by 1
+    Planet { planet: Planet },
+    // Levels up every hand by 1
+    BlackHole,
+    // Levels up the hand of the first discard of the round
+    BurntJoker { hand: PokerHand },
+}
+
+impl Planet {
+    // The poker hand the planet levels up
+    pub fn hand(self) -> PokerHand {
+        match self {
+            Planet::Pluto => PokerHand::HighCard,
+            Planet::Mercury => PokerHand::Pair,
+            Planet::Uranus => PokerHand::TwoPair,
+            Planet::Venus => PokerHand::ThreeOfAKind,
+            Planet::Saturn => PokerHand::Straight,
+            Planet::Jupiter => PokerHand::Flush,
+            Planet::Earth => PokerHand::FullHouse,
+            Planet::Mars => PokerHand::FourOfAKind,
+            Planet::Neptune => PokerHand::StraightFlush,
+            Planet::PlanetX => PokerHand::FiveOfAKind,
+            Planet::Ceres => PokerHand::FlushHouse,
+            Planet::Eris => PokerHand::FlushFive,
+        }
+    }
+}
+
+// The hand whose level is used, Royal Flush uses the Straight Flush level
+fn level_key(hand: PokerHand) -> PokerHand {
+    match hand {
+        PokerHand::NONE => PokerHand::HighCard,
+        PokerHand::RoyalFlush => PokerHand::StraightFlush,
+        _ => hand,
+    }
+}
+
+impl HandLevel {
+    // Chips and mult of a level 1 poker hand
+    fn base(hand: PokerHand) -> HandLevel {
+        let (chips, mult) = match level_key(hand) {
+            PokerHand::NONE | PokerHand::HighCard => (5.0, 1.0),
+            PokerHand::Pair => (10.0, 2.0),
+            PokerHand::TwoPair => (20.0, 2.0),
+            PokerHand::ThreeOfAKind => (30.0, 3.0),
+            PokerHand::Straight => (30.0, 4.0),
+            PokerHand::Flush => (35.0, 4.0),
+            PokerHand::FullHouse => (40.0, 4.0),
+            PokerHand::FourOfAKind => (60.0, 7.0),
+            PokerHand::StraightFlush | PokerHand::RoyalFlush => (100.0, 8.0),
+            PokerHand::FiveOfAKind => (120.0, 12.0),
+            PokerHand::FlushHouse => (140.0, 14.0),
+            PokerHand::FlushFive => (160.0, 16.0),
+        };
+        HandLevel {
+            level: 1,
+            chips,
+            mult,
+        }
+    }
+}
+
+impl HandLevels {
+    // Chips and mult gained by each level of a poker hand
+    pub fn increment(hand: PokerHand) -> (f64, f64) {
+        match level_key(hand) {
+            PokerHand::NONE | PokerHand::HighCard => (10.0, 1.0),
+            PokerHand::Pair => (15.0, 1.0),
+            PokerHand::TwoPair => (20.0, 1.0),
+            PokerHand::ThreeOfAKind => (20.0, 2.0),
+            PokerHand::Straight => (30.0, 3.0),
+            PokerHand::Flush => (15.0, 2.0),
+            PokerHand::FullHouse => (25.0, 2.0),
+            PokerHand::FourOfAKind => (30.0, 3.0),
+            PokerHand::StraightFlush | PokerHand::RoyalFlush => (40.0, 4.0),
+            PokerHand::FiveOfAKind => (35.0, 3.0),
+            PokerHand::FlushHouse => (40.0, 4.0),
+            PokerHand::FlushFive => (50.0, 3.0),
+        }
+    }
+
+    pub fn get(&self, hand: PokerHand) -> HandLevel {
+        // Saved levels from before a hand was added start at level 1
+        self.levels
+            .get(&level_key(hand))
+            .copied()
+            .unwrap_or_else(|| HandLevel::base(hand))
+    }
+
+    pub fn level_up(&mut self, hand: PokerHand, times: u32) {
+        let (chips, mult) = HandLevels::increment(hand);
+        let level = self
+            .levels
+            .entry(level_key(hand))
+            .or_insert_with(|| HandLevel::base(hand));
+        level.level += times;
+        level.chips += chips * times as f64;
+        level.mult += mult * times as f64;
+    }
+
+    pub fn apply(&mut self, upgrade: &LevelUpgrade) {
+        match upgrade {
+            LevelUpgrade::Planet { planet } => self.level_up(planet.hand(), 1),
+            LevelUpgrade::BlackHole => {
+                for hand in PokerHand::ALL {
+                    if level_key(hand) == hand {
+                        self.level_up(hand, 1);
+                    }
+                }
+            }
+            LevelUpgrade::BurntJoker { hand } => self.level_up(*hand, 1),
+        }
+    }
+}
+
+impl Default for HandLevels {
+    fn default() -> HandLevels {
+        HandLevels {
+            levels: PokerHand::ALL
+                .into_iter()
+                .filter(|&hand| level_key(hand) == hand)
+                .map(|hand| (hand, HandLevel::base(hand)))
+                .collect(),
+        }
+    }
+}
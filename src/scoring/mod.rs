@@ -0,0 +1,418 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::card::{Card, Enhancement, Seal};
+use crate::hand::{for_each_combination, Hand, Play, PokerHand};
+
+mod big;
+pub mod levels;
+#[cfg(test)]
+mod test;
+
+pub use big::BigNum;
+use levels::{HandLevel, HandLevels};
+
+/// A single change to the score, applied in the order it is triggered.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Effect {
+    Chips(f64),
+    Mult(f64),
+    XMult(f64),
+    Dollars(f64),
+    // Cards destroyed after the hand, e.g. broken Glass cards
+    Destroy(f64),
+    // Score the current card this many more times
+    Retrigger(u32),
+}
+
+/// How random effects are resolved when a hand is scored.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum Rolls {
+    // Every random effect is scaled by its probability, used to compare plays
+    Expected,
+    // Random effects are sampled from an rng with this seed
+    Seeded(u64),
+}
+
+/// The shared odds of every random effect. Jokers like Oops! All 6s change
+/// the numerator of every chance, so all rolls go through here.
+pub struct Chance {
+    // Every "1 in n" becomes "multiplier in n"
+    pub numerator_multiplier: u32,
+    rng: Option<RefCell<StdRng>>,
+}
+
+impl Chance {
+    pub fn new(numerator_multiplier: u32, rolls: Rolls) -> Chance {
+        let rng = match rolls {
+            Rolls::Expected => None,
+            Rolls::Seeded(seed) => Some(RefCell::new(StdRng::seed_from_u64(seed))),
+        };
+        Chance {
+            numerator_multiplier,
+            rng,
+        }
+    }
+
+    pub fn probability(&self, numerator: u32, denominator: u32) -> f64 {
+        ((numerator * self.numerator_multiplier) as f64 / denominator as f64).min(1.0)
+    }
+
+    // The effects if a "numerator in denominator" chance hits. Without an rng
+    // the effects are scaled to their expected value instead.
+    pub fn roll(&self, numerator: u32, denominator: u32, effects: Vec<Effect>) -> Vec<Effect> {
+        let p = self.probability(numerator, denominator);
+        match &self.rng {
+            Some(rng) => {
+                if rng.borrow_mut().gen_bool(p) {
+                    effects
+                } else {
+                    Vec::new()
+                }
+            }
+            None => effects
+                .into_iter()
+                .map(|effect| match effect {
+                    Effect::Chips(chips) => Effect::Chips(chips * p),
+                    Effect::Mult(mult) => Effect::Mult(mult * p),
+                    Effect::XMult(x) => Effect::XMult(1.0 + (x - 1.0) * p),
+                    Effect::Dollars(dollars) => Effect::Dollars(dollars * p),
+                    Effect::Destroy(count) => Effect::Destroy(count * p),
+                    Effect::Retrigger(n) => Effect::Retrigger(n),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The state of the round that affects how a hand scores.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct RoundState {
+    // This is the last hand of the round
+    pub final_hand: bool,
+    #[serde(default)]
+    pub levels: HandLevels,
+}
+
+/// Everything a joker can look at while a hand is being scored.
+pub struct ScoringContext<'a> {
+    // The poker hand that was played
+    pub hand: PokerHand,
+    // Every poker hand contained in the played cards,
+    // e.g. a Full House also contains a Pair
+    pub contained: HashMap<PokerHand, u32>,
+    pub played: &'a [Card],
+    // The played cards that score, usually the ones forming the poker hand
+    pub scoring: &'a [Card],
+    // Position in `scoring` of the card being scored, only set while the
+    // scoring cards trigger
+    pub scored_index: Cell<Option<usize>>,
+    pub held: &'a [Card],
+    pub round: &'a RoundState,
+    pub chance: Chance,
+}
+
+impl ScoringContext<'_> {
+    pub fn contains(&self, hand: PokerHand) -> bool {
+        self.contained.contains_key(&hand)
+    }
+}
+
+/// Hooks called while a hand is scored. Every hook defaults to doing nothing,
+/// so a joker only implements the ones it cares about.
+pub trait JokerEffect {
+    // Every played card scores instead of only the ones forming the hand
+    fn every_played_card_scores(&self) -> bool {
+        false
+    }
+
+    // Flushes and Straights can be made with 4 cards, like Four Fingers
+    fn four_card_hands(&self) -> bool {
+        false
+    }
+
+    // Multiplies the numerator of every chance, like Oops! All 6s
+    fn chance_multiplier(&self) -> u32 {
+        1
+    }
+
+    // Once the poker hand is known, before any card scores
+    fn on_hand_evaluated(&self, _ctx: &ScoringContext) -> Vec<Effect> {
+        Vec::new()
+    }
+
+    // Each time a scoring card is scored, including retriggers. Its position
+    // in `ctx.scoring` is `ctx.scored_index`.
+    fn on_scored_card(&self, _card: &Card, _ctx: &ScoringContext) -> Vec<Effect> {
+        Vec::new()
+    }
+
+    // Each card left in hand, after the scoring cards
+    fn on_held_card(&self, _card: &Card, _ctx: &ScoringContext) -> Vec<Effect> {
+        Vec::new()
+    }
+
+    // After every card, jokers trigger from left to right
+    fn on_end_of_hand(&self, _ctx: &ScoringContext) -> Vec<Effect> {
+        Vec::new()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Score {
+    pub chips: BigNum,
+    pub mult: BigNum,
+    // Money earned while scoring, e.g. from Gold Seals
+    #[serde(default)]
+    pub dollars: f64,
+    // Cards destroyed by the hand, can be fractional for expected rolls
+    #[serde(default)]
+    pub destroyed: f64,
+}
+
+impl Score {
+    // Chips and mult of the poker hand at its current level
+    pub fn base(level: HandLevel) -> Score {
+        Score {
+            chips: BigNum::from(level.chips),
+            mult: BigNum::from(level.mult),
+            dollars: 0.0,
+            destroyed: 0.0,
+        }
+    }
+
+    pub fn apply(&mut self, effect: &Effect) {
+        match effect {
+            Effect::Chips(chips) => self.chips += BigNum::from(*chips),
+            Effect::Mult(mult) => self.mult += BigNum::from(*mult),
+            Effect::XMult(x) => self.mult *= BigNum::from(*x),
+            Effect::Dollars(dollars) => self.dollars += dollars,
+            Effect::Destroy(count) => self.destroyed += count,
+            Effect::Retrigger(_) => {}
+        }
+    }
+
+    pub fn total(&self) -> BigNum {
+        self.chips * self.mult
+    }
+}
+
+fn retriggers(effects: &[Effect]) -> u32 {
+    effects
+        .iter()
+        .map(|effect| match effect {
+            Effect::Retrigger(n) => *n,
+            _ => 0,
+        })
+        .sum()
+}
+
+// Effects that come from the card itself each time it scores
+fn scored_card_effects(card: &Card, chance: &Chance) -> Vec<Effect> {
+    // Stone cards score a flat 50 chips in place of their rank
+    let chips = if card.is_stone() {
+        50.0
+    } else {
+        card.rank.chips() as f64
+    };
+    let mut effects = vec![Effect::Chips(chips)];
+    match card.enhancement {
+        Some(Enhancement::Bonus) => effects.push(Effect::Chips(30.0)),
+        Some(Enhancement::Mult) => effects.push(Effect::Mult(4.0)),
+        Some(Enhancement::Glass) => effects.push(Effect::XMult(2.0)),
+        Some(Enhancement::Lucky) => {
+            effects.extend(chance.roll(1, 5, vec![Effect::Mult(20.0)]));
+            effects.extend(chance.roll(1, 15, vec![Effect::Dollars(20.0)]));
+        }
+        _ => {}
+    }
+    if card.seal == Some(Seal::Gold) {
+        effects.push(Effect::Dollars(3.0));
+    }
+    effects
+}
+
+// Red Seals retrigger the card both when scored and when held
+fn seal_retriggers(card: &Card) -> u32 {
+    match card.seal {
+        Some(Seal::Red) => 1,
+        _ => 0,
+    }
+}
+
+// Effects that come from the card itself each time it triggers in hand
+fn held_card_effects(card: &Card) -> Vec<Effect> {
+    match card.enhancement {
+        Some(Enhancement::Steel) => vec![Effect::XMult(1.5)],
+        // Paid at the end of the round, but only if the card is still held
+        Some(Enhancement::Gold) => vec![Effect::Dollars(3.0)],
+        _ => Vec::new(),
+    }
+}
+
+// Triggers a card until its retriggers run out. The card's own effects and
+// every joker's are collected again on each trigger so random effects are
+// rolled each time, but retriggers only count from the first trigger.
+fn trigger_card<J: JokerEffect>(
+    score: &mut Score,
+    retrigger_count: u32,
+    jokers: &[J],
+    card_effects: impl Fn() -> Vec<Effect>,
+    hook: impl Fn(&J) -> Vec<Effect>,
+) {
+    let mut triggers = 1 + retrigger_count;
+    let mut trigger = 0;
+    while trigger < triggers {
+        let mut effects = card_effects();
+        for joker in jokers {
+            effects.extend(hook(joker));
+        }
+        if trigger == 0 {
+            triggers += retriggers(&effects);
+        }
+        for effect in effects.iter() {
+            score.apply(effect);
+        }
+        trigger += 1;
+    }
+}
+
+// The cards that aren't part of the play
+pub fn held_cards(hand: &Hand, play: &Play) -> Vec<Card> {
+    let mut held = hand.cards.clone();
+    for card in play.cards.iter() {
+        if let Some(pos) = held.iter().position(|x| x == card) {
+            held.remove(pos);
+        }
+    }
+    held
+}
+
+// Works out the poker hand of the played cards with the jokers, which only
+// matters for the ones changing what makes a hand
+pub fn play_cards<J: JokerEffect>(cards: Vec<Card>, jokers: &[J]) -> Play {
+    if jokers.iter().any(|joker| joker.four_card_hands()) {
+        Play::with_four_fingers(cards)
+    } else {
+        Play::new(cards)
+    }
+}
+
+// Picks the cards to play for the highest total score. Every combination of
+// up to 5 cards is scored with the rest of the hand held, so cards worth more
+// in hand (e.g. Steel with Baron) stay there. Random effects count with their
+// expected value. On a tie the play with the fewest cards wins.
+pub fn choose_play<J: JokerEffect>(hand: &Hand, jokers: &[J], round: &RoundState) -> Play {
+    let mut best: Option<(BigNum, Play)> = None;
+    for count in 1..=5.min(hand.cards.len()) {
+        for_each_combination(hand.cards.len(), count, |indices| {
+            let mut cards = Vec::with_capacity(count);
+            let mut held = Vec::with_capacity(hand.cards.len() - count);
+            for (i, card) in hand.cards.iter().enumerate() {
+                if indices.contains(&i) {
+                    cards.push(*card);
+                } else {
+                    held.push(*card);
+                }
+            }
+            let play = play_cards(cards, jokers);
+            let total = score_play(&play, &held, jokers, round, Rolls::Expected).total();
+            if best
+                .as_ref()
+                .is_none_or(|(best_total, _)| total > *best_total)
+            {
+                best = Some((total, play));
+            }
+        });
+    }
+    match best {
+        Some((_, play)) => play,
+        None => hand.best_play(),
+    }
+}
+
+// Scores a play with the jokers in lineup order. The cards that are not
+// played are passed as held.
+pub fn score_play<J: JokerEffect>(
+    play: &Play,
+    held: &[Card],
+    jokers: &[J],
+    round: &RoundState,
+    rolls: Rolls,
+) -> Score {
+    let scoring = if jokers.iter().any(|joker| joker.every_played_card_scores()) {
+        &play.cards
+    } else {
+        &play.forming
+    };
+    let mut contained = Hand::from(play.cards.clone()).contained_hands();
+    // Jokers like Four Fingers make hands the cards alone don't
+    for hand in PokerHand::ALL {
+        if play.hand.contains(hand) {
+            contained.entry(hand).or_insert(1);
+        }
+    }
+    let ctx = ScoringContext {
+        hand: play.hand,
+        contained,
+        played: &play.cards,
+        scoring,
+        scored_index: Cell::new(None),
+        held,
+        round,
+        chance: Chance::new(
+            jokers
+                .iter()
+                .map(|joker| joker.chance_multiplier())
+                .product(),
+            rolls,
+        ),
+    };
+    let mut score = Score::base(round.levels.get(ctx.hand));
+
+    for joker in jokers {
+        for effect in joker.on_hand_evaluated(&ctx) {
+            score.apply(&effect);
+        }
+    }
+
+    // Every retrigger replays the card's own effects followed by the jokers'
+    for (i, card) in ctx.scoring.iter().enumerate() {
+        ctx.scored_index.set(Some(i));
+        trigger_card(
+            &mut score,
+            seal_retriggers(card),
+            jokers,
+            || scored_card_effects(card, &ctx.chance),
+            |joker| joker.on_scored_card(card, &ctx),
+        );
+        // Glass breaks once after scoring, however often it was retriggered
+        if card.enhancement == Some(Enhancement::Glass) {
+            for effect in ctx.chance.roll(1, 4, vec![Effect::Destroy(1.0)]) {
+                score.apply(&effect);
+            }
+        }
+    }
+    ctx.scored_index.set(None);
+
+    for card in ctx.held {
+        trigger_card(
+            &mut score,
+            seal_retriggers(card),
+            jokers,
+            || held_card_effects(card),
+            |joker| joker.on_held_card(card, &ctx),
+        );
+    }
+
+    for joker in jokers {
+        for effect in joker.on_end_of_hand(&ctx) {
+            score.apply(&effect);
+        }
+    }
+    score
+}
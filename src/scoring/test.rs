@@ -0,0 +1,661 @@
+use crate::{
+    card::{
+        Card, Enhancement, Rank, Seal,
+        Suit::{self, Clubs, Diamonds, Hearts, Spades},
+    },
+    hand::{Hand, Play, PokerHand},
+    joker::Joker,
+    scoring::{
+        choose_play, held_cards,
+        levels::{HandLevels, LevelUpgrade, Planet},
+        play_cards, score_play, BigNum, Effect, JokerEffect, Rolls, RoundState, ScoringContext,
+    },
+};
+
+fn card(rank: Rank, suit: Suit) -> Card {
+    Card::new(rank, suit)
+}
+
+fn pair_of_kings() -> Play {
+    Play::new(vec![card(Rank::King, Hearts), card(Rank::King, Spades)])
+}
+
+#[test]
+fn scores_base_hand_and_card_chips() {
+    let score = score_play::<Joker>(
+        &pair_of_kings(),
+        &[],
+        &[],
+        &RoundState::default(),
+        Rolls::Expected,
+    );
+    assert_eq!(score.chips, 30.0);
+    assert_eq!(score.mult, 2.0);
+    assert_eq!(score.total(), 60.0);
+
+    let high_card = Play::new(vec![card(Rank::Ace, Clubs)]);
+    assert_eq!(
+        score_play::<Joker>(
+            &high_card,
+            &[],
+            &[],
+            &RoundState::default(),
+            Rolls::Expected
+        )
+        .total(),
+        16.0
+    );
+}
+
+#[test]
+fn joker_order_matters() {
+    let play = pair_of_kings();
+    let score = score_play(
+        &play,
+        &[],
+        &[Joker::Sly, Joker::Jolly, Joker::TheDuo],
+        &RoundState::default(),
+        Rolls::Expected,
+    );
+    assert_eq!(score.chips, 80.0);
+    assert_eq!(score.mult, 20.0);
+
+    let score = score_play(
+        &play,
+        &[],
+        &[Joker::Sly, Joker::TheDuo, Joker::Jolly],
+        &RoundState::default(),
+        Rolls::Expected,
+    );
+    assert_eq!(score.mult, 12.0);
+}
+
+#[test]
+fn conditional_jokers_only_trigger_on_their_hand() {
+    let play = pair_of_kings();
+    let jokers = [Joker::Zany, Joker::Crafty, Joker::TheTribe, Joker::Joker];
+    let score = score_play(&play, &[], &jokers, &RoundState::default(), Rolls::Expected);
+    assert_eq!(score.chips, 30.0);
+    assert_eq!(score.mult, 6.0);
+
+    // A full house contains a pair and three of a kind
+    let cards = vec![
+        card(Rank::Four, Clubs),
+        card(Rank::Four, Hearts),
+        card(Rank::Four, Spades),
+        card(Rank::Nine, Clubs),
+        card(Rank::Nine, Diamonds),
+    ];
+    let mut hand = Hand::from(cards);
+    let play = hand.best_play();
+    assert_eq!(play.hand, PokerHand::FullHouse);
+    let score = score_play(
+        &play,
+        &[],
+        &[Joker::Jolly, Joker::Zany, Joker::TheTrio],
+        &RoundState::default(),
+        Rolls::Expected,
+    );
+    assert_eq!(score.mult, (4.0 + 8.0 + 12.0) * 3.0);
+    assert!(hand.evaluate_poker_hands().contains_key(&PokerHand::Pair));
+}
+
+#[test]
+fn suit_jokers_trigger_per_scored_card() {
+    let play = Play::new(vec![
+        card(Rank::Five, Diamonds),
+        card(Rank::Five, Suit::Wild),
+    ]);
+    let score = score_play(
+        &play,
+        &[],
+        &[Joker::Greedy, Joker::Lusty],
+        &RoundState::default(),
+        Rolls::Expected,
+    );
+    assert_eq!(score.chips, 20.0);
+    // The wild card counts for both jokers
+    assert_eq!(score.mult, 2.0 + 3.0 + 3.0 + 3.0);
+}
+
+// Retriggers the first scoring card and gives +1 mult per held card
+struct TestJoker;
+
+impl JokerEffect for TestJoker {
+    fn on_scored_card(&self, _card: &Card, ctx: &ScoringContext) -> Vec<Effect> {
+        if ctx.scored_index.get() == Some(0) {
+            vec![Effect::Retrigger(2)]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn on_held_card(&self, _card: &Card, _ctx: &ScoringContext) -> Vec<Effect> {
+        vec![Effect::Mult(1.0)]
+    }
+}
+
+#[test]
+fn custom_jokers_can_retrigger_and_use_held_cards() {
+    let play = pair_of_kings();
+    let held = [card(Rank::Two, Clubs), card(Rank::Three, Clubs)];
+    let score = score_play(
+        &play,
+        &held,
+        &[TestJoker],
+        &RoundState::default(),
+        Rolls::Expected,
+    );
+    // The first king scores 3 times
+    assert_eq!(score.chips, 10.0 + 10.0 * 3.0 + 10.0);
+    assert_eq!(score.mult, 4.0);
+}
+
+#[test]
+fn splash_scores_every_played_card() {
+    let play = Play::new(vec![
+        card(Rank::King, Hearts),
+        card(Rank::King, Spades),
+        card(Rank::Two, Clubs),
+        card(Rank::Seven, Diamonds),
+    ]);
+    assert_eq!(play.hand, PokerHand::Pair);
+    assert_eq!(play.forming.len(), 2);
+
+    let score = score_play::<Joker>(&play, &[], &[], &RoundState::default(), Rolls::Expected);
+    assert_eq!(score.chips, 30.0);
+    let score = score_play(
+        &play,
+        &[],
+        &[Joker::Splash],
+        &RoundState::default(),
+        Rolls::Expected,
+    );
+    assert_eq!(score.chips, 30.0 + 2.0 + 7.0);
+}
+
+#[test]
+fn four_fingers_makes_hands_of_four_cards() {
+    let four_fingers = [Joker::FourFingers, Joker::Droll];
+    let mut cards: Vec<Card> = [Rank::Two, Rank::Five, Rank::Nine, Rank::King]
+        .into_iter()
+        .map(|rank| card(rank, Hearts))
+        .collect();
+    cards.push(card(Rank::Three, Spades));
+    assert_eq!(
+        play_cards::<Joker>(cards.clone(), &[]).hand,
+        PokerHand::HighCard
+    );
+    let play = play_cards(cards, &four_fingers);
+    assert_eq!(play.hand, PokerHand::Flush);
+    assert_eq!(play.forming.len(), 4);
+    // Droll Joker sees the Flush too
+    let score = score_play(
+        &play,
+        &[],
+        &four_fingers,
+        &RoundState::default(),
+        Rolls::Expected,
+    );
+    assert_eq!(score.mult, 4.0 + 10.0);
+
+    let low = vec![
+        card(Rank::Ace, Clubs),
+        card(Rank::Two, Diamonds),
+        card(Rank::Three, Hearts),
+        card(Rank::Four, Spades),
+        card(Rank::King, Clubs),
+    ];
+    let play = play_cards(low, &four_fingers);
+    assert_eq!(play.hand, PokerHand::Straight);
+    assert!(!play.forming.contains(&card(Rank::King, Clubs)));
+
+    let straight_flush = vec![
+        card(Rank::Five, Hearts),
+        card(Rank::Six, Hearts),
+        card(Rank::Seven, Hearts),
+        card(Rank::Eight, Hearts),
+        card(Rank::King, Clubs),
+    ];
+    let play = play_cards(straight_flush, &four_fingers);
+    assert_eq!(play.hand, PokerHand::StraightFlush);
+    assert_eq!(play.forming.len(), 4);
+
+    // A Full House still beats a Flush of four
+    let full_house = vec![
+        card(Rank::Nine, Hearts),
+        card(Rank::Nine, Spades),
+        card(Rank::Nine, Clubs),
+        card(Rank::Two, Clubs),
+        card(Rank::Two, Diamonds),
+    ];
+    assert_eq!(
+        play_cards(full_house, &four_fingers).hand,
+        PokerHand::FullHouse
+    );
+}
+
+#[test]
+fn four_fingers_plays_flushes_of_four_cards() {
+    let hand = Hand::from(vec![
+        card(Rank::Two, Hearts),
+        card(Rank::Five, Hearts),
+        card(Rank::Nine, Hearts),
+        card(Rank::King, Hearts),
+        card(Rank::Three, Spades),
+        card(Rank::Seven, Clubs),
+    ]);
+    let play = choose_play(&hand, &[Joker::FourFingers], &RoundState::default());
+    assert_eq!(play.hand, PokerHand::Flush);
+    assert!(play.forming.iter().all(|card| card.suit == Hearts));
+}
+
+#[test]
+fn splash_plays_kickers() {
+    let hand = Hand::from(vec![
+        card(Rank::King, Hearts),
+        card(Rank::King, Spades),
+        card(Rank::Two, Clubs),
+        card(Rank::Seven, Diamonds),
+        card(Rank::Ace, Clubs),
+        card(Rank::Four, Diamonds),
+        card(Rank::Nine, Hearts),
+    ]);
+    let play = choose_play::<Joker>(&hand, &[], &RoundState::default());
+    assert_eq!(play.cards.len(), 2);
+
+    let play = choose_play(&hand, &[Joker::Splash], &RoundState::default());
+    assert_eq!(play.hand, PokerHand::Pair);
+    assert_eq!(play.cards.len(), 5);
+    assert_eq!(held_cards(&hand, &play).len(), 2);
+    let score = score_play(
+        &play,
+        &[],
+        &[Joker::Splash],
+        &RoundState::default(),
+        Rolls::Expected,
+    );
+    assert_eq!(score.chips, 30.0 + 11.0 + 9.0 + 7.0);
+}
+
+#[test]
+fn red_seal_retriggers_and_gold_seal_pays() {
+    let round = RoundState::default();
+    let play = Play::new(vec![
+        card(Rank::King, Hearts).with_seal(Seal::Red),
+        card(Rank::King, Spades).with_seal(Seal::Gold),
+    ]);
+    let score = score_play(&play, &[], &[Joker::Greedy], &round, Rolls::Expected);
+    assert_eq!(score.chips, 10.0 + 10.0 * 2.0 + 10.0);
+    assert_eq!(score.dollars, 3.0);
+
+    // A red seal retriggers held card effects too
+    let held = [card(Rank::Two, Clubs).with_seal(Seal::Red)];
+    let score = score_play(
+        &pair_of_kings(),
+        &held,
+        &[TestJoker],
+        &round,
+        Rolls::Expected,
+    );
+    assert_eq!(score.mult, 2.0 + 2.0);
+}
+
+#[test]
+fn retrigger_jokers_only_trigger_on_their_cards() {
+    let round = RoundState::default();
+    let play = Play::new(vec![
+        card(Rank::Five, Hearts),
+        card(Rank::Five, Spades),
+        card(Rank::King, Clubs),
+        card(Rank::King, Diamonds),
+    ]);
+    assert_eq!(play.hand, PokerHand::TwoPair);
+
+    let score = score_play(&play, &[], &[Joker::Hack], &round, Rolls::Expected);
+    assert_eq!(score.chips, 20.0 + 5.0 * 4.0 + 10.0 * 2.0);
+    let score = score_play(&play, &[], &[Joker::SockAndBuskin], &round, Rolls::Expected);
+    assert_eq!(score.chips, 20.0 + 5.0 * 2.0 + 10.0 * 4.0);
+    // Dusk only retriggers on the last hand of the round
+    let score = score_play(&play, &[], &[Joker::Dusk], &round, Rolls::Expected);
+    assert_eq!(score.chips, 20.0 + 5.0 * 2.0 + 10.0 * 2.0);
+    let final_hand = RoundState {
+        final_hand: true,
+        ..RoundState::default()
+    };
+    let score = score_play(&play, &[], &[Joker::Dusk], &final_hand, Rolls::Expected);
+    assert_eq!(score.chips, 20.0 + 5.0 * 4.0 + 10.0 * 4.0);
+}
+
+#[test]
+fn hanging_chad_only_retriggers_the_first_card() {
+    // Identical cards, so only the position tells them apart
+    let play = Play::new(vec![card(Rank::Nine, Hearts), card(Rank::Nine, Hearts)]);
+    let score = score_play(
+        &play,
+        &[],
+        &[Joker::HangingChad],
+        &RoundState::default(),
+        Rolls::Expected,
+    );
+    assert_eq!(score.chips, 10.0 + 9.0 * 3.0 + 9.0);
+}
+
+#[test]
+fn retriggers_stack() {
+    let five = card(Rank::Five, Hearts).with_seal(Seal::Red);
+    let play = Play::new(vec![five, card(Rank::Five, Spades)]);
+    let jokers = [Joker::Hack, Joker::Dusk, Joker::HangingChad, Joker::Lusty];
+    let score = score_play(
+        &play,
+        &[],
+        &jokers,
+        &RoundState {
+            final_hand: true,
+            ..RoundState::default()
+        },
+        Rolls::Expected,
+    );
+    // The first five triggers 1 + 1 (seal) + 1 (Hack) + 1 (Dusk) + 2 (Hanging Chad)
+    // times and the second 1 + 1 (Hack) + 1 (Dusk) times
+    assert_eq!(score.chips, 10.0 + 5.0 * 6.0 + 5.0 * 3.0);
+    assert_eq!(score.mult, 2.0 + 3.0 * 6.0);
+}
+
+#[test]
+fn enhancements_score_when_played_or_held() {
+    let round = RoundState::default();
+    let play = Play::new(vec![
+        card(Rank::King, Hearts).with_enhancement(Enhancement::Bonus),
+        card(Rank::King, Spades).with_enhancement(Enhancement::Mult),
+    ]);
+    let held = [
+        card(Rank::Two, Clubs).with_enhancement(Enhancement::Steel),
+        card(Rank::Three, Clubs).with_enhancement(Enhancement::Gold),
+    ];
+    let score = score_play::<Joker>(&play, &held, &[], &round, Rolls::Expected);
+    assert_eq!(score.chips, 30.0 + 30.0);
+    assert_eq!(score.mult, (2.0 + 4.0) * 1.5);
+    assert_eq!(score.dollars, 3.0);
+
+    // Steel does nothing when played and Bonus does nothing when held
+    let play = Play::new(vec![held[0], held[0]]);
+    let score = score_play::<Joker>(
+        &play,
+        &[play.cards[0].with_enhancement(Enhancement::Bonus)],
+        &[],
+        &round,
+        Rolls::Expected,
+    );
+    assert_eq!(score.total(), (10.0 + 4.0) * 2.0);
+}
+
+#[test]
+fn held_jokers_trigger_per_held_card() {
+    let round = RoundState::default();
+    let held = [
+        card(Rank::King, Clubs),
+        card(Rank::King, Diamonds).with_enhancement(Enhancement::Steel),
+        card(Rank::Queen, Clubs),
+    ];
+    let score = score_play(
+        &pair_of_kings(),
+        &held,
+        &[Joker::Baron],
+        &round,
+        Rolls::Expected,
+    );
+    assert_eq!(score.mult, 2.0 * 1.5 * 1.5 * 1.5);
+    let score = score_play(
+        &pair_of_kings(),
+        &held,
+        &[Joker::ShootTheMoon],
+        &round,
+        Rolls::Expected,
+    );
+    // Held cards trigger in order, so the queen comes after the steel king
+    assert_eq!(score.mult, 2.0 * 1.5 + 13.0);
+}
+
+#[test]
+fn choose_play_maximizes_total_score() {
+    let round = RoundState::default();
+    let sevens = [card(Rank::Seven, Clubs), card(Rank::Seven, Diamonds)];
+    let mut hand = Hand::from(vec![card(Rank::King, Hearts), card(Rank::King, Spades)]);
+    hand.cards.extend(sevens);
+    let play = choose_play::<Joker>(&hand, &[], &round);
+    assert_eq!(play.hand, PokerHand::TwoPair);
+
+    // Holding steel kings with Baron is worth more than playing them
+    let steel_king = |suit| card(Rank::King, suit).with_enhancement(Enhancement::Steel);
+    let mut hand = Hand::from(vec![steel_king(Hearts), steel_king(Spades)]);
+    hand.cards.extend(sevens);
+    let play = choose_play(&hand, &[Joker::Baron], &round);
+    assert_eq!(play.hand, PokerHand::Pair);
+    assert_eq!(play.cards, sevens);
+    let held = held_cards(&hand, &play);
+    let score = score_play(&play, &held, &[Joker::Baron], &round, Rolls::Expected);
+    assert_eq!(score.total(), (10.0 + 7.0 + 7.0) * 2.0 * 1.5_f64.powi(4));
+}
+
+#[test]
+fn expected_rolls_scale_random_effects() {
+    let round = RoundState::default();
+    let play = Play::new(vec![
+        card(Rank::Ten, Hearts).with_enhancement(Enhancement::Lucky),
+        card(Rank::Ten, Spades).with_enhancement(Enhancement::Glass),
+    ]);
+    let score = score_play::<Joker>(&play, &[], &[], &round, Rolls::Expected);
+    assert_eq!(score.mult, (2.0 + 20.0 / 5.0) * 2.0);
+    assert_eq!(score.dollars, 20.0 / 15.0);
+    assert_eq!(score.destroyed, 0.25);
+
+    // Oops! All 6s doubles every chance, including Bloodstone's
+    let jokers = [Joker::OopsAllSixes, Joker::Bloodstone];
+    let score = score_play(&play, &[], &jokers, &round, Rolls::Expected);
+    assert_eq!(score.mult, (2.0 + 40.0 / 5.0) * 1.5 * 2.0);
+    assert_eq!(score.destroyed, 0.5);
+}
+
+#[test]
+fn space_joker_levels_up_the_hand_before_scoring() {
+    let round = RoundState::default();
+    let space = [Joker::SpaceJoker];
+    let leveled = (30.0 + 15.0) * (2.0 + 1.0);
+    let mut hits = 0;
+    for seed in 0..200 {
+        let score = score_play(&pair_of_kings(), &[], &space, &round, Rolls::Seeded(seed));
+        if score.total() == leveled {
+            hits += 1;
+        } else {
+            assert_eq!(score.total(), 60.0);
+        }
+    }
+    assert!((25..=75).contains(&hits), "Space Joker hit {} times", hits);
+
+    let seeded = score_play(&pair_of_kings(), &[], &space, &round, Rolls::Seeded(3));
+    let again = score_play(&pair_of_kings(), &[], &space, &round, Rolls::Seeded(3));
+    assert_eq!(seeded, again);
+}
+
+#[test]
+fn big_numbers_match_f64_while_they_fit() {
+    let chips = BigNum::from(1234.5);
+    let mult = BigNum::from(0.1) + BigNum::from(0.2);
+    assert_eq!(chips * mult, 1234.5 * (0.1 + 0.2));
+    assert_eq!((chips * mult / 3.0).to_f64(), 1234.5 * (0.1 + 0.2) / 3.0);
+    assert!(BigNum::from(-5.0) < BigNum::from(2.0));
+    assert!(BigNum::from(2.0) < BigNum::from(3.0));
+    assert_eq!(BigNum::from(0.0), BigNum::ZERO);
+}
+
+#[test]
+fn adding_zero_keeps_small_numbers() {
+    // Zero's exponent is bigger than a tiny number's, it still has to add up
+    let tiny = BigNum::from(1e-300) * BigNum::from(1e-300);
+    assert_eq!(BigNum::ZERO + tiny, tiny);
+    assert_eq!(tiny + BigNum::ZERO, tiny);
+    let mut sum = BigNum::ZERO;
+    sum += BigNum::from(2f64.powi(-100));
+    assert_eq!(sum, 2f64.powi(-100));
+}
+
+#[test]
+fn big_numbers_grow_past_f64() {
+    let mut score = BigNum::from(1e300);
+    score *= BigNum::from(1e300);
+    assert!(score > BigNum::from(f64::MAX));
+    assert_eq!(score.to_f64(), f64::INFINITY);
+    assert!((score.log10() - 600.0).abs() < 1e-9);
+    assert_eq!(score.to_string(), "1.000e600");
+    assert_eq!(format!("{:>10}", BigNum::from(1234.0)), "      1234");
+    assert_eq!(BigNum::from(123456789012.0).to_string(), "1.235e11");
+
+    // Serialized as a string that keeps the precision
+    let exact = score.to_exact_string();
+    assert_eq!(BigNum::parse(&exact).unwrap(), score);
+    assert!((BigNum::parse("1e600").unwrap().log10() - 600.0).abs() < 1e-12);
+    assert_eq!(BigNum::parse("60").unwrap(), 60.0);
+    assert_eq!(
+        BigNum::parse(&BigNum::from(0.1).to_exact_string()).unwrap(),
+        0.1
+    );
+    assert!(BigNum::parse("lots").is_err());
+}
+
+#[test]
+fn big_numbers_round_trip_exactly() {
+    // Every bit of the mantissa matters, a decimal exponent would round some
+    let score = BigNum::pow10(400.0) * BigNum::from(1.2345678901234567);
+    let json = serde_json::to_string(&score).unwrap();
+    assert!(
+        json.contains("*2^"),
+        "Expected the binary form, got {}",
+        json
+    );
+    assert_eq!(serde_json::from_str::<BigNum>(&json).unwrap(), score);
+    let tiny = BigNum::from(1e-300) * BigNum::from(1e-300);
+    assert_eq!(BigNum::parse(&tiny.to_exact_string()).unwrap(), tiny);
+
+    // Plain numbers stay plain
+    assert_eq!(
+        serde_json::to_string(&BigNum::from(60.0)).unwrap(),
+        "\"60\""
+    );
+    assert_eq!(serde_json::from_str::<BigNum>("60").unwrap(), 60.0);
+}
+
+#[test]
+fn scores_do_not_overflow() {
+    let round = RoundState::default();
+    let held = [card(Rank::King, Clubs); 1000];
+    let score = score_play(
+        &pair_of_kings(),
+        &held,
+        &[Joker::Baron],
+        &round,
+        Rolls::Expected,
+    );
+    // 1.5^1000 is about 1.2e176, squared by a second Baron is past f64
+    assert!((score.mult.log10() - (2.0f64.log10() + 1000.0 * 1.5f64.log10())).abs() < 1e-9);
+    let jokers = [Joker::Baron, Joker::Baron];
+    let score = score_play(&pair_of_kings(), &held, &jokers, &round, Rolls::Expected);
+    assert_eq!(score.total().to_f64(), f64::INFINITY);
+    assert!((score.total().log10() - (60.0f64.log10() + 2000.0 * 1.5f64.log10())).abs() < 1e-9);
+}
+
+#[test]
+fn planets_level_up_their_hand() {
+    let mut levels = HandLevels::default();
+    levels.apply(&LevelUpgrade::Planet {
+        planet: Planet::Mercury,
+    });
+    levels.apply(&LevelUpgrade::Planet {
+        planet: Planet::Mercury,
+    });
+    let pair = levels.get(PokerHand::Pair);
+    assert_eq!(pair.level, 3);
+    assert_eq!((pair.chips, pair.mult), (10.0 + 2.0 * 15.0, 2.0 + 2.0));
+
+    levels.apply(&LevelUpgrade::Planet {
+        planet: Planet::Pluto,
+    });
+    assert_eq!(levels.get(PokerHand::HighCard).chips, 15.0);
+    // Royal Flush shares its level with Straight Flush
+    levels.apply(&LevelUpgrade::Planet {
+        planet: Planet::Neptune,
+    });
+    assert_eq!(levels.get(PokerHand::RoyalFlush).level, 2);
+    assert_eq!(levels.get(PokerHand::StraightFlush).mult, 12.0);
+}
+
+#[test]
+fn black_hole_and_burnt_joker_level_up() {
+    let mut levels = HandLevels::default();
+    levels.apply(&LevelUpgrade::BlackHole);
+    assert_eq!(levels.get(PokerHand::HighCard).level, 2);
+    for hand in PokerHand::ALL {
+        assert_eq!(levels.get(hand).level, 2, "{:?}", hand);
+    }
+    let five = levels.get(PokerHand::FiveOfAKind);
+    assert_eq!((five.chips, five.mult), (120.0 + 35.0, 12.0 + 3.0));
+    let flush_five = levels.get(PokerHand::FlushFive);
+    assert_eq!(
+        (flush_five.chips, flush_five.mult),
+        (160.0 + 50.0, 16.0 + 3.0)
+    );
+
+    levels.apply(&LevelUpgrade::BurntJoker {
+        hand: PokerHand::FlushHouse,
+    });
+    assert_eq!(levels.get(PokerHand::FlushHouse).level, 3);
+    assert_eq!(levels.get(PokerHand::FlushHouse).chips, 140.0 + 80.0);
+}
+
+#[test]
+fn hand_levels_round_trip_through_json() {
+    let mut levels = HandLevels::default();
+    levels.apply(&LevelUpgrade::Planet {
+        planet: Planet::Jupiter,
+    });
+    // Keyed by the hand names, the same object the page saves
+    let json = serde_json::to_value(&levels).unwrap();
+    assert_eq!(json["levels"]["Flush"]["level"], 2);
+    let saved = serde_json::to_string(&levels).unwrap();
+    assert_eq!(serde_json::from_str::<HandLevels>(&saved).unwrap(), levels);
+}
+
+#[test]
+fn hand_levels_feed_into_scoring() {
+    let mut round = RoundState::default();
+    round.levels.level_up(PokerHand::Pair, 4);
+    let score = score_play::<Joker>(&pair_of_kings(), &[], &[], &round, Rolls::Expected);
+    assert_eq!(score.chips, 10.0 + 4.0 * 15.0 + 20.0);
+    assert_eq!(score.mult, 2.0 + 4.0);
+}
+
+#[test]
+fn stone_cards_score_flat_chips_without_a_suit() {
+    let stone = card(Rank::Ace, Hearts).with_enhancement(Enhancement::Stone);
+    let mut cards = pair_of_kings().cards;
+    cards.push(stone);
+    let play = Play::new(cards);
+    assert_eq!(play.hand, PokerHand::Pair);
+    // The Stone card scores even though it isn't part of the pair, and isn't
+    // a heart for Lusty Joker
+    let score = score_play(
+        &play,
+        &[],
+        &[Joker::Lusty],
+        &RoundState::default(),
+        Rolls::Expected,
+    );
+    assert_eq!(score.chips, 10.0 + 20.0 + 50.0);
+    assert_eq!(score.mult, 2.0 + 3.0);
+
+    // Only Stone cards is no hand, which scores like a High Card
+    let play = Play::new(vec![stone]);
+    assert_eq!(play.hand, PokerHand::NONE);
+    let score = score_play::<Joker>(&play, &[], &[], &RoundState::default(), Rolls::Expected);
+    assert_eq!(score.total(), (5.0 + 50.0) * 1.0);
+}
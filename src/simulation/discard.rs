@@ -0,0 +1,88 @@
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
+use super::exact::{distinct_draws, exact_odds_given, EXACT_LIMIT};
+use super::{draw_trials_given, Threads};
+use crate::card::Card;
+use crate::deck::Deck;
+use crate::hand::{Hand, PokerHand};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DiscardOdds {
+    // Cards to throw away, the rest of the hand is kept
+    pub discard: Vec<Card>,
+    // Odds of holding the target after drawing back up to the hand size
+    pub odds: f64,
+    // Whether every draw was gone through, otherwise the odds are from trials
+    pub exact: bool,
+}
+
+// Every way to pick up to `max` of the cards, skipping picks that only differ
+// by copies of the same card
+fn discard_choices(cards: &[Card], max: usize) -> BTreeSet<Vec<Card>> {
+    let mut choices = BTreeSet::new();
+    let mut picked = Vec::new();
+    pick(cards, max, &mut picked, &mut choices);
+    choices
+}
+
+fn pick(cards: &[Card], max: usize, picked: &mut Vec<Card>, choices: &mut BTreeSet<Vec<Card>>) {
+    let mut choice = picked.clone();
+    choice.sort();
+    choices.insert(choice);
+    if picked.len() == max {
+        return;
+    }
+    for (i, card) in cards.iter().enumerate() {
+        picked.push(*card);
+        pick(&cards[i + 1..], max, picked, choices);
+        picked.pop();
+    }
+}
+
+// Ranks every way to discard up to `max_discards` cards from the hand by the
+// odds of holding the target once the discards are replaced from the deck,
+// best first, keeping more cards when the odds are tied. The deck should be
+// the remaining draw pile, i.e. it should not contain the hand. A discard is
+// worked out exactly when it has no more draws than `trials`, otherwise from
+// `trials` random draws.
+pub fn advise_discards(
+    hand: &Hand,
+    deck: &Deck,
+    target: PokerHand,
+    max_discards: u8,
+    trials: u32,
+    threads: Threads,
+) -> Result<Vec<DiscardOdds>, String> {
+    let mut advice = Vec::new();
+    for discard in discard_choices(&hand.cards, max_discards as usize) {
+        let mut held = hand.cards.clone();
+        for card in discard.iter() {
+            if let Some(pos) = held.iter().position(|x| x == card) {
+                held.remove(pos);
+            }
+        }
+        let held = Hand::from(held);
+        // Only what's left of the deck can be drawn
+        let to_draw = discard.len().min(deck.size()) as u8;
+        let exact = distinct_draws(deck, to_draw) <= (trials as u64).min(EXACT_LIMIT);
+        let odds = if exact {
+            exact_odds_given(&held, deck, to_draw, threads)?
+        } else {
+            draw_trials_given(&held, deck, to_draw, trials, threads)?
+        };
+        advice.push(DiscardOdds {
+            discard,
+            odds: odds[&target],
+            exact,
+        });
+    }
+
+    advice.sort_by(|a, b| {
+        b.odds
+            .total_cmp(&a.odds)
+            .then(a.discard.len().cmp(&b.discard.len()))
+    });
+    Ok(advice)
+}
@@ -0,0 +1,135 @@
+use serde::{Deserialize, Serialize};
+
+use crate::scoring::BigNum;
+
+// Percentiles reported when none are asked for
+pub const DEFAULT_PERCENTILES: [f64; 5] = [10.0, 25.0, 50.0, 75.0, 90.0];
+// Scores grow exponentially, so the histogram bins are spaced on a log scale
+const HISTOGRAM_BINS: usize = 16;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Percentile {
+    // Between 0 and 100
+    pub percentile: f64,
+    pub score: BigNum,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HistogramBin {
+    // Scores from low up to but not including high, the last bin includes high
+    pub low: BigNum,
+    pub high: BigNum,
+    pub count: u32,
+}
+
+/// Summary of the scores from a number of trials.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ScoreDistribution {
+    pub trials: u32,
+    pub min: BigNum,
+    pub max: BigNum,
+    pub mean: BigNum,
+    pub median: BigNum,
+    pub percentiles: Vec<Percentile>,
+    pub histogram: Vec<HistogramBin>,
+}
+
+impl ScoreDistribution {
+    pub fn from_scores(mut scores: Vec<BigNum>, percentiles: &[f64]) -> ScoreDistribution {
+        scores.sort_by(BigNum::total_cmp);
+        let n = scores.len();
+        if n == 0 {
+            return ScoreDistribution {
+                trials: 0,
+                min: BigNum::ZERO,
+                max: BigNum::ZERO,
+                mean: BigNum::ZERO,
+                median: BigNum::ZERO,
+                percentiles: Vec::new(),
+                histogram: Vec::new(),
+            };
+        }
+
+        let median = if n.is_multiple_of(2) {
+            (scores[n / 2 - 1] + scores[n / 2]) / 2.0
+        } else {
+            scores[n / 2]
+        };
+        ScoreDistribution {
+            trials: n as u32,
+            min: scores[0],
+            max: scores[n - 1],
+            mean: scores.iter().fold(BigNum::ZERO, |sum, &score| sum + score) / n as f64,
+            median,
+            percentiles: percentiles
+                .iter()
+                .map(|&percentile| Percentile {
+                    percentile,
+                    score: nearest_rank(&scores, percentile),
+                })
+                .collect(),
+            histogram: log_histogram(&scores),
+        }
+    }
+
+    // The score at the percentile, if it was asked for
+    pub fn percentile(&self, percentile: f64) -> Option<BigNum> {
+        self.percentiles
+            .iter()
+            .find(|p| p.percentile == percentile)
+            .map(|p| p.score)
+    }
+
+    // One block character per histogram bin, scaled to the fullest bin
+    pub fn sparkline(&self) -> String {
+        const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+        let most = self
+            .histogram
+            .iter()
+            .map(|bin| bin.count)
+            .max()
+            .unwrap_or(0);
+        self.histogram
+            .iter()
+            .map(|bin| match bin.count {
+                0 => ' ',
+                count => BLOCKS[(count as usize * BLOCKS.len()).div_ceil(most as usize) - 1],
+            })
+            .collect()
+    }
+}
+
+// The smallest score that at least `percentile` percent of the sorted scores
+// are less than or equal to
+fn nearest_rank(sorted: &[BigNum], percentile: f64) -> BigNum {
+    let rank = (percentile.clamp(0.0, 100.0) / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+// Scores below 1 are counted in the first bin
+fn log_histogram(sorted: &[BigNum]) -> Vec<HistogramBin> {
+    let log10 = |score: &BigNum| score.log10().max(0.0);
+    let low = log10(&sorted[0]);
+    let high = log10(&sorted[sorted.len() - 1]);
+    if high <= low {
+        return vec![HistogramBin {
+            low: sorted[0],
+            high: sorted[sorted.len() - 1],
+            count: sorted.len() as u32,
+        }];
+    }
+
+    let width = (high - low) / HISTOGRAM_BINS as f64;
+    let mut histogram: Vec<HistogramBin> = (0..HISTOGRAM_BINS)
+        .map(|i| HistogramBin {
+            low: BigNum::pow10(low + width * i as f64),
+            high: BigNum::pow10(low + width * (i + 1) as f64),
+            count: 0,
+        })
+        .collect();
+    for score in sorted {
+        let bin = ((log10(score) - low) / width).floor() as usize;
+        histogram[bin.min(HISTOGRAM_BINS - 1)].count += 1;
+    }
+    histogram
+}
@@ -0,0 +1,163 @@
+use std::collections::{BTreeMap, HashMap};
+
+use super::{reduce_trials, to_probabilities, Threads};
+use crate::card::Card;
+use crate::deck::Deck;
+use crate::hand::{Hand, PokerHand};
+
+// Distinct draws worth going through before it's quicker to run trials
+pub const EXACT_LIMIT: u64 = 5_000_000;
+
+// Identical cards make identical draws, so the exact odds go through each
+// distinct card once and weight its draws by the ways to pick its copies
+fn card_counts(deck: &Deck) -> Vec<(Card, u64)> {
+    let mut counts: BTreeMap<Card, u64> = BTreeMap::new();
+    for card in deck.cards() {
+        *counts.entry(*card).or_insert(0) += 1;
+    }
+    counts.into_iter().collect()
+}
+
+// Ways to pick k of n cards, as a float since big decks overflow integers
+fn binomial(n: u64, k: u64) -> f64 {
+    (0..k).fold(1.0, |acc, i| acc * (n - i) as f64 / (i + 1) as f64)
+}
+
+// Number of different hands of `hand_size` cards the deck can deal, where
+// copies of the same card make the same hand
+pub fn distinct_draws(deck: &Deck, hand_size: u8) -> u64 {
+    let n = hand_size as usize;
+    // ways[j] is the number of hands of j cards from the cards seen so far
+    let mut ways = vec![0u64; n + 1];
+    ways[0] = 1;
+    for (_, copies) in card_counts(deck) {
+        let mut next = vec![0u64; n + 1];
+        for (j, next) in next.iter_mut().enumerate() {
+            for taken in 0..=(copies as usize).min(j) {
+                *next = next.saturating_add(ways[j - taken]);
+            }
+        }
+        ways = next;
+    }
+    ways[n]
+}
+
+// Calls `visit` with every distinct draw of `left` more cards from `counts`,
+// and the number of ways to draw it. `remaining` is the number of cards in
+// `counts`, so branches that can't be completed are skipped.
+fn for_each_draw<F>(
+    counts: &[(Card, u64)],
+    remaining: u64,
+    left: u64,
+    drawn: &mut Vec<Card>,
+    weight: f64,
+    visit: &mut F,
+) where
+    F: FnMut(&[Card], f64),
+{
+    if left == 0 {
+        visit(drawn, weight);
+        return;
+    }
+    let Some((&(card, copies), rest)) = counts.split_first() else {
+        return;
+    };
+    if remaining < left {
+        return;
+    }
+    for taken in 0..=copies.min(left) {
+        drawn.extend(std::iter::repeat_n(card, taken as usize));
+        for_each_draw(
+            rest,
+            remaining - copies,
+            left - taken,
+            drawn,
+            weight * binomial(copies, taken),
+            visit,
+        );
+        drawn.truncate(drawn.len() - taken as usize);
+    }
+}
+
+// Exact odds of each poker hand when the held cards are kept and `to_draw`
+// more cards are drawn from the deck, going through every possible draw. The
+// deck should not contain the held cards. Errors when fewer than `to_draw`
+// cards are left or there are more than EXACT_LIMIT distinct draws.
+pub fn exact_odds_given(
+    held: &Hand,
+    deck: &Deck,
+    to_draw: u8,
+    threads: Threads,
+) -> Result<HashMap<PokerHand, f64>, String> {
+    if to_draw == 0 {
+        return Ok(to_probabilities(&held.contained_hands(), 1));
+    }
+    deck.check_hand_size(to_draw)?;
+    let draws = distinct_draws(deck, to_draw);
+    if draws > EXACT_LIMIT {
+        return Err(format!(
+            "{} different draws are too many to go through, run trials instead",
+            draws
+        ));
+    }
+
+    // Each task starts its draws with some copies of one card, and only draws
+    // cards after it, so together they go through every draw once
+    let counts = card_counts(deck);
+    let left = to_draw as u64;
+    let tasks: Vec<(usize, u64)> = counts
+        .iter()
+        .enumerate()
+        .flat_map(|(i, &(_, copies))| (1..=copies.min(left)).map(move |taken| (i, taken)))
+        .collect();
+    let weights = reduce_trials(
+        0..tasks.len() as u32,
+        threads,
+        |task| {
+            let (i, taken) = tasks[task as usize];
+            let (card, copies) = counts[i];
+            let rest = &counts[i + 1..];
+            let remaining = rest.iter().map(|(_, copies)| copies).sum();
+            let mut weights: HashMap<PokerHand, f64> = HashMap::new();
+            let mut drawn = vec![card; taken as usize];
+            for_each_draw(
+                rest,
+                remaining,
+                left - taken,
+                &mut drawn,
+                binomial(copies, taken),
+                &mut |drawn, weight| {
+                    let mut hand = Hand::from(drawn.to_vec());
+                    hand.cards.extend_from_slice(&held.cards);
+                    for hand in hand.contained_hands().into_keys() {
+                        *weights.entry(hand).or_insert(0.0) += weight;
+                    }
+                },
+            );
+            weights
+        },
+        HashMap::new,
+        |mut acc, res| {
+            for (hand, weight) in res {
+                *acc.entry(hand).or_insert(0.0) += weight;
+            }
+            acc
+        },
+    );
+
+    let total = binomial(deck.size() as u64, left);
+    Ok(PokerHand::ALL
+        .into_iter()
+        .map(|hand| (hand, weights.get(&hand).copied().unwrap_or(0.0) / total))
+        .collect())
+}
+
+// Exact odds of each poker hand in a hand of `hand_size` cards, the exact
+// counterpart of draw_trials. Errors like exact_odds_given.
+pub fn exact_odds(
+    deck: &Deck,
+    hand_size: u8,
+    threads: Threads,
+) -> Result<HashMap<PokerHand, f64>, String> {
+    exact_odds_given(&Hand::from(Vec::new()), deck, hand_size, threads)
+}
@@ -0,0 +1,339 @@
+use std::collections::HashMap;
+use std::ops::Range;
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::deck::Deck;
+use crate::hand::{Hand, PokerHand};
+use crate::scoring::{self, BigNum, JokerEffect, Rolls, RoundState};
+use crate::utils::statistics::mix_seed;
+use distribution::ScoreDistribution;
+
+pub mod discard;
+pub mod distribution;
+pub mod exact;
+pub mod progress;
+pub mod results;
+pub mod round;
+pub mod search;
+
+#[cfg(test)]
+mod test;
+
+fn merge_counts(
+    mut acc: HashMap<PokerHand, u32>,
+    res: HashMap<PokerHand, u32>,
+) -> HashMap<PokerHand, u32> {
+    // Combine results from each trial
+    for (&k, &v) in res.iter() {
+        *acc.entry(k).or_insert(0) += v;
+    }
+    acc
+}
+
+/// Where trials run. Browsers can only run rayon's workers on pages that can
+/// share memory, so elsewhere the trials run on the calling thread.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Threads {
+    Pool,
+    Single,
+}
+
+// Runs the trials in the range and combines their results, which are the
+// same either way as long as `combine` doesn't depend on the order
+fn reduce_trials<T, F, I, C>(
+    trials: Range<u32>,
+    threads: Threads,
+    trial: F,
+    identity: I,
+    combine: C,
+) -> T
+where
+    T: Send,
+    F: Fn(u32) -> T + Sync + Send,
+    I: Fn() -> T + Sync + Send,
+    C: Fn(T, T) -> T + Sync + Send,
+{
+    match threads {
+        Threads::Pool => trials.into_par_iter().map(trial).reduce(identity, combine),
+        Threads::Single => trials.map(trial).fold(identity(), combine),
+    }
+}
+
+// Runs the trials in the range and adds up the counts
+fn count_trials<F>(trials: Range<u32>, threads: Threads, trial: F) -> HashMap<PokerHand, u32>
+where
+    F: Fn(u32) -> HashMap<PokerHand, u32> + Sync + Send,
+{
+    reduce_trials(trials, threads, trial, HashMap::new, merge_counts)
+}
+
+// Runs the trials in the range and returns their results in trial order
+fn collect_trials<T, F>(trials: Range<u32>, threads: Threads, trial: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(u32) -> T + Sync + Send,
+{
+    match threads {
+        Threads::Pool => trials.into_par_iter().map(trial).collect(),
+        Threads::Single => trials.map(trial).collect(),
+    }
+}
+
+// Every poker hand gets an entry, including the ones that never came up
+fn to_probabilities(counts: &HashMap<PokerHand, u32>, trials: u32) -> HashMap<PokerHand, f64> {
+    PokerHand::ALL
+        .into_iter()
+        .map(|hand| {
+            let count = counts.get(&hand).copied().unwrap_or(0);
+            (hand, count as f64 / trials.max(1) as f64)
+        })
+        .collect()
+}
+
+// Draws `trials` hands from the deck and returns how often each poker hand
+// could be made from the drawn cards. Errors when the deck is empty or
+// smaller than the hand.
+pub fn draw_trials(
+    deck: &Deck,
+    hand_size: u8,
+    trials: u32,
+    threads: Threads,
+) -> Result<HashMap<PokerHand, f64>, String> {
+    deck.check_hand_size(hand_size)?;
+    let net_result = count_trials(0..trials, threads, |_| {
+        deck.draw_hand(hand_size).contained_hands()
+    });
+
+    Ok(to_probabilities(&net_result, trials))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BestHandOdds {
+    // Odds of each poker hand being the best one in the drawn cards, these
+    // add up to 1
+    pub best: HashMap<PokerHand, f64>,
+    // Odds of the best hand ranking at least as high as each poker hand
+    pub at_least: HashMap<PokerHand, f64>,
+}
+
+// Draws `trials` hands from the deck and returns how often each poker hand
+// was the best one that could be made. Errors like draw_trials.
+pub fn best_hand_trials(
+    deck: &Deck,
+    hand_size: u8,
+    trials: u32,
+    threads: Threads,
+) -> Result<BestHandOdds, String> {
+    deck.check_hand_size(hand_size)?;
+    let net_result = count_trials(0..trials, threads, |_| {
+        HashMap::from([(deck.draw_hand(hand_size).best_hand(), 1)])
+    });
+
+    let best = to_probabilities(&net_result, trials);
+    Ok(BestHandOdds {
+        at_least: at_least(&best),
+        best,
+    })
+}
+
+// Turns best hand odds into the odds of the best hand ranking at least as
+// high as each poker hand, e.g. "at least a Flush" also counts a Full House
+pub fn at_least(best: &HashMap<PokerHand, f64>) -> HashMap<PokerHand, f64> {
+    PokerHand::ALL
+        .into_iter()
+        .map(|hand| {
+            let odds = best
+                .iter()
+                .filter(|(other, _)| **other >= hand)
+                .map(|(_, odds)| odds)
+                .sum();
+            (hand, odds)
+        })
+        .collect()
+}
+
+// Odds of each poker hand when the held cards are kept and `to_draw` more
+// cards are drawn from the deck. The deck should be the remaining draw pile,
+// i.e. it should not contain the held cards. Errors when fewer than `to_draw`
+// cards are left.
+pub fn draw_trials_given(
+    held: &Hand,
+    deck: &Deck,
+    to_draw: u8,
+    trials: u32,
+    threads: Threads,
+) -> Result<HashMap<PokerHand, f64>, String> {
+    if to_draw == 0 {
+        // Nothing left to draw, the outcome is already known
+        return Ok(to_probabilities(&held.contained_hands(), 1));
+    }
+    deck.check_hand_size(to_draw)?;
+
+    let net_result = count_trials(0..trials, threads, |_| {
+        let mut hand = deck.draw_hand(to_draw);
+        hand.cards.extend_from_slice(&held.cards);
+        hand.contained_hands()
+    });
+
+    Ok(to_probabilities(&net_result, trials))
+}
+
+/// Scores of hands drawn, played and scored with a set of jokers.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ScoreTrials {
+    // Every trial, whatever poker hand was played
+    pub overall: ScoreDistribution,
+    // The trials that played each poker hand
+    pub by_hand: HashMap<PokerHand, ScoreDistribution>,
+    // Average number of Glass cards destroyed per hand
+    pub glass_destroyed: f64,
+}
+
+// Draws hands, plays the highest scoring cards with the jokers and the hand
+// levels of the round, and samples the random effects. The same seed always
+// gives the same scores.
+#[allow(clippy::too_many_arguments)]
+pub fn score_trials<J: JokerEffect + Sync>(
+    deck: &Deck,
+    hand_size: u8,
+    jokers: &[J],
+    round: &RoundState,
+    trials: u32,
+    seed: u64,
+    percentiles: &[f64],
+    threads: Threads,
+) -> ScoreTrials {
+    let results: Vec<(PokerHand, BigNum, f64)> = collect_trials(0..trials, threads, |trial| {
+        let trial_seed = mix_seed(seed, trial as u64);
+        let hand = deck.draw_hand_seeded(hand_size, trial_seed);
+        let play = scoring::choose_play(&hand, jokers, round);
+        let held = scoring::held_cards(&hand, &play);
+        let score = scoring::score_play(&play, &held, jokers, round, Rolls::Seeded(trial_seed));
+        (play.hand, score.total(), score.destroyed)
+    });
+
+    let mut hand_scores: HashMap<PokerHand, Vec<BigNum>> = HashMap::new();
+    for (hand, score, _) in results.iter() {
+        hand_scores.entry(*hand).or_default().push(*score);
+    }
+    let scores: Vec<BigNum> = results.iter().map(|(_, score, _)| *score).collect();
+    let destroyed: f64 = results.iter().map(|(_, _, destroyed)| destroyed).sum();
+
+    ScoreTrials {
+        overall: ScoreDistribution::from_scores(scores, percentiles),
+        by_hand: hand_scores
+            .into_iter()
+            .map(|(hand, scores)| (hand, ScoreDistribution::from_scores(scores, percentiles)))
+            .collect(),
+        glass_destroyed: destroyed / trials.max(1) as f64,
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HandSizeOdds {
+    pub hand_size: u8,
+    pub odds: HashMap<PokerHand, f64>,
+}
+
+// Computes the odds for every hand size in the range in a single pass.
+// Each trial draws the largest hand in a random order, and every smaller hand
+// size is evaluated on a prefix of it, which is itself a uniformly random hand.
+// Errors when the range is empty or the largest hand doesn't fit in the deck.
+pub fn sweep_hand_sizes(
+    deck: &Deck,
+    min_hand_size: u8,
+    max_hand_size: u8,
+    trials: u32,
+    threads: Threads,
+) -> Result<Vec<HandSizeOdds>, String> {
+    let min_hand_size = min_hand_size.max(1);
+    if min_hand_size > max_hand_size {
+        return Err(format!(
+            "No hand sizes from {} to {}",
+            min_hand_size, max_hand_size
+        ));
+    }
+    deck.check_hand_size(max_hand_size)?;
+    let sizes = (max_hand_size - min_hand_size) as usize + 1;
+
+    let net_result: Vec<HashMap<PokerHand, u32>> = reduce_trials(
+        0..trials,
+        threads,
+        |_| {
+            let drawn = deck.draw_hand(max_hand_size);
+            (min_hand_size..=max_hand_size)
+                .map(|size| Hand::from(drawn.cards[..size as usize].to_vec()).contained_hands())
+                .collect::<Vec<_>>()
+        },
+        || vec![HashMap::new(); sizes],
+        |acc, res| {
+            acc.into_iter()
+                .zip(res)
+                .map(|(acc, res)| merge_counts(acc, res))
+                .collect()
+        },
+    );
+
+    Ok(net_result
+        .iter()
+        .zip(min_hand_size..=max_hand_size)
+        .map(|(counts, hand_size)| HandSizeOdds {
+            hand_size,
+            odds: to_probabilities(counts, trials),
+        })
+        .collect())
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DeckComparison {
+    pub base: HashMap<PokerHand, f64>,
+    pub other: HashMap<PokerHand, f64>,
+    // other - base for every poker hand
+    pub delta: HashMap<PokerHand, f64>,
+}
+
+// Compares the odds of two decks using common random numbers: each trial
+// draws from both decks with the same seed, so cards the decks share are drawn
+// together and the delta reflects the difference between the decks rather
+// than the sampling noise of two separate runs.
+pub fn compare_decks(
+    base: &Deck,
+    other: &Deck,
+    hand_size: u8,
+    trials: u32,
+    seed: u64,
+    threads: Threads,
+) -> DeckComparison {
+    let (base_counts, other_counts) = reduce_trials(
+        0..trials,
+        threads,
+        |i| {
+            let trial_seed = mix_seed(seed, i as u64);
+            (
+                base.draw_hand_seeded(hand_size, trial_seed)
+                    .contained_hands(),
+                other
+                    .draw_hand_seeded(hand_size, trial_seed)
+                    .contained_hands(),
+            )
+        },
+        || (HashMap::new(), HashMap::new()),
+        |(base_acc, other_acc), (base_res, other_res)| {
+            (
+                merge_counts(base_acc, base_res),
+                merge_counts(other_acc, other_res),
+            )
+        },
+    );
+
+    let base = to_probabilities(&base_counts, trials);
+    let other = to_probabilities(&other_counts, trials);
+    let mut delta = HashMap::new();
+    for hand in base.keys().chain(other.keys()) {
+        let diff = other.get(hand).unwrap_or(&0.0) - base.get(hand).unwrap_or(&0.0);
+        delta.insert(*hand, diff);
+    }
+    DeckComparison { base, other, delta }
+}
@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+use std::mem;
+
+use serde::{Deserialize, Serialize};
+
+use crate::deck::Deck;
+use crate::hand::PokerHand;
+use crate::simulation::results::{count_seeded_trials, PokerHandResults};
+use crate::simulation::{merge_counts, Threads};
+use crate::utils::clock::now_ms;
+
+/// Odds of every poker hand worked out a batch of trials at a time, so a UI
+/// can show results as they come in and stop early. Once every trial has run
+/// the counts are the same as `draw_trial_results` with the same seed.
+#[derive(Debug, Clone)]
+pub struct Simulation {
+    deck: Deck,
+    hand_size: u8,
+    seed: u64,
+    trials: u32,
+    completed: u32,
+    counts: HashMap<PokerHand, u32>,
+    cancelled: bool,
+    elapsed_ms: f64,
+}
+
+/// The results of the trials run so far.
+#[cfg_attr(all(target_arch = "wasm32", feature = "wasm"), derive(tsify::Tsify))]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SimulationSnapshot {
+    pub completed: u32,
+    pub total: u32,
+    // Between 0 and 1
+    pub progress: f64,
+    pub done: bool,
+    pub cancelled: bool,
+    pub results: PokerHandResults,
+}
+
+impl Simulation {
+    pub fn new(deck: Deck, hand_size: u8, trials: u32, seed: u64) -> Simulation {
+        Simulation {
+            deck,
+            hand_size,
+            seed,
+            trials,
+            completed: 0,
+            counts: HashMap::new(),
+            cancelled: false,
+            elapsed_ms: 0.0,
+        }
+    }
+
+    // Runs up to `n` more trials and returns how many ran, 0 once done
+    pub fn step(&mut self, n: u32, threads: Threads) -> u32 {
+        if self.is_done() {
+            return 0;
+        }
+        let start = now_ms();
+        let end = self.completed.saturating_add(n).min(self.trials);
+        let counts = count_seeded_trials(
+            &self.deck,
+            self.hand_size,
+            self.seed,
+            self.completed..end,
+            threads,
+        );
+        self.counts = merge_counts(mem::take(&mut self.counts), counts);
+        let ran = end - self.completed;
+        self.completed = end;
+        self.elapsed_ms += now_ms() - start;
+        ran
+    }
+
+    // Stops the simulation, the trials that already ran stay in the results
+    pub fn cancel(&mut self) {
+        self.cancelled = true;
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.cancelled || self.completed >= self.trials
+    }
+
+    pub fn completed(&self) -> u32 {
+        self.completed
+    }
+
+    pub fn progress(&self) -> f64 {
+        if self.trials == 0 {
+            return 1.0;
+        }
+        self.completed as f64 / self.trials as f64
+    }
+
+    pub fn snapshot(&self) -> SimulationSnapshot {
+        SimulationSnapshot {
+            completed: self.completed,
+            total: self.trials,
+            progress: self.progress(),
+            done: self.is_done(),
+            cancelled: self.cancelled,
+            results: PokerHandResults::from_counts(
+                &self.counts,
+                self.hand_size,
+                self.completed,
+                self.seed,
+                self.elapsed_ms,
+            ),
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+
+use crate::deck::Deck;
+use crate::hand::PokerHand;
+use crate::simulation::{count_trials, Threads};
+use crate::utils::clock::now_ms;
+use crate::utils::statistics::{mix_seed, wilson_interval};
+
+// Bumped whenever a field is added, removed or changes meaning
+pub const RESULTS_VERSION: u32 = 1;
+// Confidence level of the intervals
+pub const CONFIDENCE: f64 = 0.95;
+const Z_95: f64 = 1.959964;
+
+#[cfg_attr(all(target_arch = "wasm32", feature = "wasm"), derive(tsify::Tsify))]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct ConfidenceInterval {
+    pub low: f64,
+    pub high: f64,
+}
+
+/// How often a poker hand could be made from the drawn cards.
+#[cfg_attr(all(target_arch = "wasm32", feature = "wasm"), derive(tsify::Tsify))]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PokerHandResult {
+    // The name, not the numeric PokerHand enum of the JS bindings
+    #[cfg_attr(
+        all(target_arch = "wasm32", feature = "wasm"),
+        tsify(type = "PokerHandName")
+    )]
+    pub hand: PokerHand,
+    // Trials the hand could be made in
+    pub count: u32,
+    pub probability: f64,
+    pub ci: ConfidenceInterval,
+}
+
+/// Odds of every poker hand from a run of trials, in a shape that stays the
+/// same within a `version`.
+#[cfg_attr(all(target_arch = "wasm32", feature = "wasm"), derive(tsify::Tsify))]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PokerHandResults {
+    pub version: u32,
+    pub hand_size: u8,
+    pub trials: u32,
+    // Running again with the same seed and deck gives the same counts
+    #[cfg_attr(all(target_arch = "wasm32", feature = "wasm"), tsify(type = "bigint"))]
+    pub seed: u64,
+    pub confidence: f64,
+    pub elapsed_ms: f64,
+    // One entry for every poker hand, from the lowest to the highest ranked
+    pub results: Vec<PokerHandResult>,
+}
+
+impl PokerHandResults {
+    pub fn from_counts(
+        counts: &HashMap<PokerHand, u32>,
+        hand_size: u8,
+        trials: u32,
+        seed: u64,
+        elapsed_ms: f64,
+    ) -> PokerHandResults {
+        let results = PokerHand::ALL
+            .into_iter()
+            .map(|hand| {
+                let count = counts.get(&hand).copied().unwrap_or(0);
+                let (low, high) = wilson_interval(count, trials, Z_95);
+                PokerHandResult {
+                    hand,
+                    count,
+                    probability: count as f64 / trials.max(1) as f64,
+                    ci: ConfidenceInterval { low, high },
+                }
+            })
+            .collect();
+        PokerHandResults {
+            version: RESULTS_VERSION,
+            hand_size,
+            trials,
+            seed,
+            confidence: CONFIDENCE,
+            elapsed_ms,
+            results,
+        }
+    }
+}
+
+// Counts the poker hands in the trials of the range. Each trial draws from
+// its own seed, so splitting the trials up doesn't change the counts.
+pub(crate) fn count_seeded_trials(
+    deck: &Deck,
+    hand_size: u8,
+    seed: u64,
+    trials: Range<u32>,
+    threads: Threads,
+) -> HashMap<PokerHand, u32> {
+    count_trials(trials, threads, |i| {
+        deck.draw_hand_seeded(hand_size, mix_seed(seed, i as u64))
+            .contained_hands()
+    })
+}
+
+// Draws `trials` seeded hands and counts how often each poker hand could be
+// made, like `draw_trials` but with counts, intervals and run details
+pub fn draw_trial_results(
+    deck: &Deck,
+    hand_size: u8,
+    trials: u32,
+    seed: u64,
+    threads: Threads,
+) -> PokerHandResults {
+    let start = now_ms();
+    let counts = count_seeded_trials(deck, hand_size, seed, 0..trials, threads);
+    PokerHandResults::from_counts(&counts, hand_size, trials, seed, now_ms() - start)
+}
@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+use std::mem;
+
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+
+use crate::card::Card;
+use crate::deck::Deck;
+use crate::hand::{Hand, Play, PokerHand};
+use crate::simulation::{merge_counts, reduce_trials, to_probabilities, Threads};
+
+/// How the cards to play are picked from the hand each turn.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(tag = "policy")]
+pub enum PlayPolicy {
+    // Always play the best poker hand available
+    BestHand,
+    // Play the target when it's available, otherwise throw away the lowest
+    // cards to dig for it
+    Chase { target: PokerHand },
+}
+
+impl PlayPolicy {
+    pub fn from_str(s: &str, target: PokerHand) -> Result<PlayPolicy, &'static str> {
+        match s {
+            "best" | "BestHand" => Ok(PlayPolicy::BestHand),
+            "chase" | "Chase" => Ok(PlayPolicy::Chase { target }),
+            _ => Err("Invalid play policy"),
+        }
+    }
+
+    pub fn choose_play(&self, hand: &Hand) -> Play {
+        match self {
+            PlayPolicy::BestHand => hand.best_play(),
+            PlayPolicy::Chase { target } => {
+                let best = hand.best_play();
+                if best.hand == *target {
+                    return best;
+                }
+                let mut cards = hand.cards.clone();
+                cards.sort_by_key(|card| card.rank);
+                cards.truncate(5);
+                Play::new(cards)
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct PlayOdds {
+    // Odds of each poker hand being in hand before this play
+    pub available: HashMap<PokerHand, f64>,
+    // Odds of each poker hand being the one played, High Card plays included
+    pub played: HashMap<PokerHand, f64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RoundOdds {
+    pub plays: Vec<PlayOdds>,
+    // Odds of the target being available for at least one play in the round
+    pub target_hit: f64,
+}
+
+#[derive(Clone, Default)]
+struct PlayCounts {
+    available: HashMap<PokerHand, u32>,
+    played: HashMap<PokerHand, u32>,
+}
+
+// Plays one round and returns what was seen on each play and whether the
+// target was available at any point
+fn play_round(
+    deck: &Deck,
+    hand_size: u8,
+    hands_left: u8,
+    target: PokerHand,
+    policy: &PlayPolicy,
+) -> (Vec<PlayCounts>, bool) {
+    let mut draw_pile: Vec<Card> = deck.cards().to_vec();
+    draw_pile.shuffle(&mut rand::thread_rng());
+    let mut hand = Hand::from(Vec::new());
+    let mut plays = Vec::with_capacity(hands_left as usize);
+    let mut target_hit = false;
+
+    for _ in 0..hands_left {
+        // Draw back up to the hand size from what's left
+        while hand.cards.len() < hand_size as usize {
+            match draw_pile.pop() {
+                Some(card) => hand.cards.push(card),
+                None => break,
+            }
+        }
+        if hand.cards.is_empty() {
+            break;
+        }
+
+        let available = hand.contained_hands();
+        target_hit |= available.contains_key(&target);
+        let play = policy.choose_play(&hand);
+        plays.push(PlayCounts {
+            available,
+            played: HashMap::from([(play.hand, 1)]),
+        });
+
+        // The played cards are gone for the rest of the round
+        for card in play.cards.iter() {
+            if let Some(pos) = hand.cards.iter().position(|x| x == card) {
+                hand.cards.remove(pos);
+            }
+        }
+    }
+    (plays, target_hit)
+}
+
+// Simulates rounds of `hands_left` plays, where the played cards are removed
+// and the hand is refilled from the same draw pile between plays
+pub fn simulate_round(
+    deck: &Deck,
+    hand_size: u8,
+    hands_left: u8,
+    target: PokerHand,
+    policy: &PlayPolicy,
+    trials: u32,
+    threads: Threads,
+) -> RoundOdds {
+    let (play_counts, hits) = reduce_trials(
+        0..trials,
+        threads,
+        |_| {
+            let (plays, hit) = play_round(deck, hand_size, hands_left, target, policy);
+            (plays, hit as u32)
+        },
+        || (Vec::new(), 0),
+        |(mut acc, acc_hits), (res, res_hits)| {
+            if acc.len() < res.len() {
+                acc.resize(res.len(), PlayCounts::default());
+            }
+            for (acc, res) in acc.iter_mut().zip(res) {
+                acc.available = merge_counts(mem::take(&mut acc.available), res.available);
+                acc.played = merge_counts(mem::take(&mut acc.played), res.played);
+            }
+            (acc, acc_hits + res_hits)
+        },
+    );
+
+    RoundOdds {
+        plays: play_counts
+            .iter()
+            .map(|counts| PlayOdds {
+                available: to_probabilities(&counts.available, trials),
+                played: to_probabilities(&counts.played, trials),
+            })
+            .collect(),
+        target_hit: hits as f64 / trials as f64,
+    }
+}
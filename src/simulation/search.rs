@@ -0,0 +1,228 @@
+use std::collections::HashSet;
+use std::mem;
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::card::{Card, Suit};
+use crate::deck::{Deck, DeckOp};
+use crate::hand::PokerHand;
+use crate::simulation::results::count_seeded_trials;
+use crate::simulation::Threads;
+
+// How many of the best candidates are expanded at each step of the search
+const BEAM_WIDTH: usize = 8;
+
+/// The deck modifications the search is allowed to make.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(tag = "kind")]
+pub enum OpBudget {
+    // Destroy up to `count` cards, e.g. two Hanged Man destroys
+    Destroy { count: usize },
+    // Convert up to `max_cards` cards to a single suit, e.g. The Star
+    SuitChange { max_cards: usize },
+    // Raise the rank of up to `max_cards` cards, e.g. Strength
+    RankUp { max_cards: usize },
+}
+
+impl OpBudget {
+    fn steps(&self) -> usize {
+        match self {
+            OpBudget::Destroy { count } => *count,
+            OpBudget::SuitChange { max_cards } | OpBudget::RankUp { max_cards } => *max_cards,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Improvement {
+    // The ops to apply in order, indices refer to the deck after the previous op
+    pub ops: Vec<DeckOp>,
+    // The cards the ops target in the order they were picked, as they were
+    // when picked, so a card raised twice shows up once for each rank
+    pub cards: Vec<Card>,
+    pub probability: f64,
+    // Change in probability compared to the unmodified deck
+    pub delta: f64,
+}
+
+#[derive(Clone)]
+struct Candidate {
+    deck: Deck,
+    ops: Vec<DeckOp>,
+    cards: Vec<Card>,
+    // Only set for suit changes, every card must go to the same suit
+    suit: Option<Suit>,
+}
+
+// Probability of drawing the target hand, every call with the same seed draws
+// the same hands so candidates can be compared with little noise
+fn target_probability(
+    deck: &Deck,
+    target: PokerHand,
+    hand_size: u8,
+    trials: u32,
+    seed: u64,
+) -> f64 {
+    // The candidates are already scored in parallel
+    let counts = count_seeded_trials(deck, hand_size, seed, 0..trials, Threads::Single);
+    counts.get(&target).copied().unwrap_or(0) as f64 / trials.max(1) as f64
+}
+
+// The selected cards of the ops the search makes and how many the tarot can
+// select at once
+fn selection(op: &mut DeckOp) -> Option<(&mut Vec<usize>, usize)> {
+    match op {
+        DeckOp::Strength { cards } | DeckOp::HangedMan { cards } => Some((cards, 2)),
+        DeckOp::Star { cards }
+        | DeckOp::Moon { cards }
+        | DeckOp::Sun { cards }
+        | DeckOp::World { cards } => Some((cards, 3)),
+        _ => None,
+    }
+}
+
+// Adds a single card op, folding it into the last op when that is the same
+// tarot and can select another card, so e.g. three cards changed to Hearts
+// are one Sun rather than three
+fn push_grouped(ops: &mut Vec<DeckOp>, mut op: DeckOp) {
+    let Some(last) = ops
+        .last_mut()
+        .filter(|last| mem::discriminant(*last) == mem::discriminant(&op))
+    else {
+        ops.push(op);
+        return;
+    };
+    let destroys = matches!(last, DeckOp::HangedMan { .. });
+    let (Some((selected, max)), Some((new, _))) = (selection(last), selection(&mut op)) else {
+        ops.push(op);
+        return;
+    };
+    let mut index = new[0];
+    if destroys {
+        // The index is into the deck after the last op's destroys, move it
+        // back past them to index the deck the last op applies to
+        let mut destroyed = selected.clone();
+        destroyed.sort_unstable();
+        for i in destroyed {
+            if i <= index {
+                index += 1;
+            }
+        }
+    }
+    // A Strength raises each card once, a second raise needs another one
+    if selected.len() < max && !selected.contains(&index) {
+        selected.push(index);
+    } else {
+        ops.push(op);
+    }
+}
+
+fn expand(candidate: &Candidate, budget: &OpBudget) -> Vec<Candidate> {
+    let mut expanded = Vec::new();
+    let mut seen = HashSet::new();
+    for (i, card) in candidate.deck.cards().iter().enumerate() {
+        // Identical cards give identical decks
+        if !seen.insert(*card) {
+            continue;
+        }
+        let moves: Vec<(DeckOp, Option<Suit>)> = match budget {
+            OpBudget::Destroy { .. } => vec![(DeckOp::HangedMan { cards: vec![i] }, None)],
+            OpBudget::RankUp { .. } => vec![(DeckOp::Strength { cards: vec![i] }, None)],
+            OpBudget::SuitChange { .. } => {
+                [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades]
+                    .into_iter()
+                    .filter(|suit| *suit != card.suit)
+                    .filter(|suit| candidate.suit.is_none_or(|s| s == *suit))
+                    .filter_map(|suit| {
+                        DeckOp::suit_change(suit, vec![i]).map(|op| (op, Some(suit)))
+                    })
+                    .collect()
+            }
+        };
+        for (op, suit) in moves {
+            let mut next = candidate.clone();
+            if next.deck.apply(&op).is_err() {
+                continue;
+            }
+            push_grouped(&mut next.ops, op);
+            next.cards.push(*card);
+            next.suit = suit.or(candidate.suit);
+            expanded.push(next);
+        }
+    }
+    expanded
+}
+
+// Searches for the deck modifications within the budget that give the best
+// odds of the target hand, returning up to `top` results with the best first.
+// This is a beam search, each step keeps the best `BEAM_WIDTH` decks and
+// tries one more op on each of them.
+#[allow(clippy::too_many_arguments)]
+pub fn search_improvements(
+    deck: &Deck,
+    budget: &OpBudget,
+    target: PokerHand,
+    hand_size: u8,
+    trials: u32,
+    seed: u64,
+    top: usize,
+    threads: Threads,
+) -> Vec<Improvement> {
+    let baseline = target_probability(deck, target, hand_size, trials, seed);
+    let mut beam = vec![Candidate {
+        deck: deck.clone(),
+        ops: Vec::new(),
+        cards: Vec::new(),
+        suit: None,
+    }];
+    let mut results: Vec<Improvement> = Vec::new();
+    let mut visited: HashSet<Vec<Card>> = HashSet::new();
+
+    for _ in 0..budget.steps() {
+        let candidates: Vec<Candidate> = beam
+            .iter()
+            .flat_map(|candidate| expand(candidate, budget))
+            .filter(|candidate| {
+                // Different orders of the same ops end up with the same deck
+                let mut cards = candidate.deck.cards().to_vec();
+                cards.sort();
+                visited.insert(cards)
+            })
+            .collect();
+        if candidates.is_empty() {
+            break;
+        }
+
+        let score = |candidate: Candidate| {
+            let probability = target_probability(&candidate.deck, target, hand_size, trials, seed);
+            (probability, candidate)
+        };
+        let mut scored: Vec<(f64, Candidate)> = match threads {
+            Threads::Pool => candidates.into_par_iter().map(score).collect(),
+            Threads::Single => candidates.into_iter().map(score).collect(),
+        };
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        results.extend(scored.iter().map(|(probability, candidate)| Improvement {
+            ops: candidate.ops.clone(),
+            cards: candidate.cards.clone(),
+            probability: *probability,
+            delta: probability - baseline,
+        }));
+        beam = scored
+            .into_iter()
+            .take(BEAM_WIDTH)
+            .map(|(_, candidate)| candidate)
+            .collect();
+    }
+
+    // Prefer fewer ops when the odds are the same
+    results.sort_by(|a, b| {
+        b.probability
+            .total_cmp(&a.probability)
+            .then(a.ops.len().cmp(&b.ops.len()))
+    });
+    results.truncate(top);
+    results
+}
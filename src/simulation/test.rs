@@ -0,0 +1,612 @@
+use std::collections::HashMap;
+
+use crate::{
+    card::{Card, Enhancement, Rank, Suit},
+    deck::{Deck, DeckOp, DeckPreset},
+    hand::{Hand, PokerHand},
+    joker::Joker,
+    scoring::{BigNum, RoundState},
+    simulation::{
+        at_least, best_hand_trials, compare_decks,
+        discard::advise_discards,
+        distribution::{ScoreDistribution, DEFAULT_PERCENTILES},
+        draw_trials, draw_trials_given,
+        exact::{distinct_draws, exact_odds, exact_odds_given},
+        progress::Simulation,
+        results::{draw_trial_results, PokerHandResults, RESULTS_VERSION},
+        round::{simulate_round, PlayPolicy},
+        score_trials,
+        search::{search_improvements, OpBudget},
+        sweep_hand_sizes, Threads,
+    },
+};
+
+fn suited(suit: Suit, ranks: &[usize]) -> Vec<Card> {
+    ranks
+        .iter()
+        .map(|rank| Card::new(Rank::from(*rank), suit))
+        .collect()
+}
+
+#[test]
+fn comparing_a_deck_with_itself_has_no_noise() {
+    let deck = Deck::new();
+    let comparison = compare_decks(&deck, &deck.clone(), 8, 2000, 11, Threads::Pool);
+    assert!(!comparison.delta.is_empty());
+    for (hand, delta) in comparison.delta.iter() {
+        assert_eq!(*delta, 0.0, "Expected no difference for {:?}", hand);
+    }
+}
+
+#[test]
+fn seeded_trials_are_the_same_on_one_thread() {
+    let base = Deck::new();
+    let other = Deck::from_preset(DeckPreset::Abandoned);
+    let pool = compare_decks(&base, &other, 8, 500, 9, Threads::Pool);
+    let single = compare_decks(&base, &other, 8, 500, 9, Threads::Single);
+    assert_eq!(pool.delta, single.delta);
+
+    let round = RoundState::default();
+    let score = |threads| score_trials(&base, 8, &[Joker::Joker], &round, 200, 9, &[50.0], threads);
+    let (pool, single) = (score(Threads::Pool), score(Threads::Single));
+    assert_eq!(pool.overall.mean, single.overall.mean);
+    assert_eq!(pool.overall.percentiles, single.overall.percentiles);
+}
+
+#[test]
+fn comparison_is_reproducible_with_a_seed() {
+    let base = Deck::new();
+    let other = Deck::from_preset(DeckPreset::Abandoned);
+    let first = compare_decks(&base, &other, 8, 1000, 3, Threads::Pool);
+    let second = compare_decks(&base, &other, 8, 1000, 3, Threads::Pool);
+    assert_eq!(first.delta, second.delta);
+}
+
+#[test]
+fn suit_changes_improve_flush_odds() {
+    let base = Deck::new();
+    let mut other = base.clone();
+    // Turn six clubs into hearts
+    other
+        .apply(&DeckOp::Sun {
+            cards: vec![0, 1, 2],
+        })
+        .unwrap();
+    other
+        .apply(&DeckOp::Sun {
+            cards: vec![3, 4, 5],
+        })
+        .unwrap();
+    assert_eq!(other.count_suit(&Suit::Hearts), 19);
+
+    let comparison = compare_decks(&base, &other, 8, 5000, 5, Threads::Pool);
+    assert!(comparison.delta[&PokerHand::Flush] > 0.0);
+}
+
+#[test]
+fn destroy_search_removes_off_suit_cards() {
+    let mut cards = suited(Suit::Hearts, &[2, 4, 6, 8, 10, 12, 14]);
+    cards.extend(suited(Suit::Clubs, &[3, 9]));
+    let deck = Deck::from(cards);
+
+    let budget = OpBudget::Destroy { count: 2 };
+    let results = search_improvements(
+        &deck,
+        &budget,
+        PokerHand::Flush,
+        5,
+        500,
+        1,
+        5,
+        Threads::Pool,
+    );
+    assert_eq!(results.len(), 5);
+    assert!(results
+        .windows(2)
+        .all(|pair| pair[0].probability >= pair[1].probability));
+
+    let best = &results[0];
+    assert_eq!(best.probability, 1.0);
+    assert!(best.delta > 0.0);
+    // Both destroys fit in one Hanged Man
+    assert_eq!(best.ops.len(), 1);
+    assert!(matches!(&best.ops[0], DeckOp::HangedMan { cards } if cards.len() == 2));
+    assert!(best.cards.iter().all(|card| card.suit == Suit::Clubs));
+
+    let mut improved = deck.clone();
+    for op in best.ops.iter() {
+        improved.apply(op).unwrap();
+    }
+    assert_eq!(improved.count_suit(&Suit::Hearts), improved.size() as u64);
+}
+
+#[test]
+fn suit_change_search_converts_to_one_suit() {
+    let mut cards = suited(Suit::Hearts, &[2, 5, 9, 13]);
+    cards.extend(suited(Suit::Spades, &[3, 7, 11]));
+    let deck = Deck::from(cards);
+
+    let budget = OpBudget::SuitChange { max_cards: 3 };
+    let results = search_improvements(
+        &deck,
+        &budget,
+        PokerHand::Flush,
+        5,
+        500,
+        2,
+        3,
+        Threads::Pool,
+    );
+    let best = &results[0];
+    assert_eq!(best.probability, 1.0);
+    assert!(best.cards.iter().all(|card| card.suit == Suit::Spades));
+    assert_eq!(best.ops.len(), 1);
+    assert!(matches!(&best.ops[0], DeckOp::Sun { cards } if cards.len() == 3));
+}
+
+#[test]
+fn rank_up_search_groups_cards_within_the_strength_limit() {
+    let mut cards = suited(Suit::Hearts, &[2, 3, 4, 5]);
+    cards.extend(suited(Suit::Spades, &[2, 3, 4, 6]));
+    let deck = Deck::from(cards);
+
+    let budget = OpBudget::RankUp { max_cards: 3 };
+    let results = search_improvements(
+        &deck,
+        &budget,
+        PokerHand::FourOfAKind,
+        5,
+        200,
+        4,
+        50,
+        Threads::Pool,
+    );
+    let three = results
+        .iter()
+        .find(|improvement| improvement.cards.len() == 3)
+        .unwrap();
+    // Three raises need a second Strength
+    assert_eq!(three.ops.len(), 2);
+    let mut improved = deck.clone();
+    for op in three.ops.iter() {
+        improved.apply(op).unwrap();
+    }
+    assert_eq!(improved.size(), deck.size());
+}
+
+#[test]
+fn sweep_covers_every_hand_size() {
+    let deck = Deck::new();
+    let sweep = sweep_hand_sizes(&deck, 5, 12, 2000, Threads::Pool).unwrap();
+    let sizes: Vec<u8> = sweep.iter().map(|odds| odds.hand_size).collect();
+    assert_eq!(sizes, (5..=12).collect::<Vec<u8>>());
+
+    // Every hand that has a pair still has it with more cards, so the odds
+    // can only go up with the hand size
+    for pair in sweep.windows(2) {
+        let smaller = pair[0].odds.get(&PokerHand::Pair).unwrap_or(&0.0);
+        let larger = pair[1].odds.get(&PokerHand::Pair).unwrap_or(&0.0);
+        assert!(smaller <= larger);
+    }
+}
+
+#[test]
+fn sweep_stops_at_the_deck_size() {
+    let deck = Deck::from(suited(Suit::Hearts, &[2, 3, 4, 5, 6, 7]));
+    let sweep = sweep_hand_sizes(&deck, 5, 6, 100, Threads::Pool).unwrap();
+    assert_eq!(sweep.len(), 2);
+    assert_eq!(sweep[1].hand_size, 6);
+    assert_eq!(sweep[1].odds[&PokerHand::StraightFlush], 1.0);
+
+    assert!(sweep_hand_sizes(&deck, 5, 12, 100, Threads::Pool).is_err());
+    assert!(sweep_hand_sizes(&deck, 6, 5, 100, Threads::Pool).is_err());
+}
+
+#[test]
+fn trials_reject_hands_larger_than_the_deck() {
+    let deck = Deck::from(suited(Suit::Hearts, &[2, 3, 4]));
+    assert!(draw_trials(&deck, 4, 10, Threads::Pool).is_err());
+    assert!(best_hand_trials(&deck, 4, 10, Threads::Pool).is_err());
+    assert!(best_hand_trials(&Deck::from(Vec::new()), 0, 10, Threads::Pool).is_err());
+
+    // Drawing the whole deck is still allowed
+    let held = Hand::from(Vec::new());
+    assert!(draw_trials_given(&held, &deck, 4, 10, Threads::Pool).is_err());
+    let odds = draw_trials_given(&held, &deck, 3, 10, Threads::Pool).unwrap();
+    assert_eq!(odds[&PokerHand::HighCard], 1.0);
+}
+
+#[test]
+fn odds_cover_every_hand_including_high_card() {
+    let deck = Deck::new();
+    let odds = draw_trials(&deck, 1, 200, Threads::Pool).unwrap();
+    assert_eq!(odds.len(), PokerHand::ALL.len());
+    assert_eq!(odds[&PokerHand::HighCard], 1.0);
+    assert_eq!(odds[&PokerHand::Pair], 0.0);
+    assert_eq!(odds[&PokerHand::NONE], 0.0);
+
+    // A hand of only Stone cards makes no hand at all
+    let stones = suited(Suit::Spades, &[2, 3, 4])
+        .into_iter()
+        .map(|card| card.with_enhancement(Enhancement::Stone))
+        .collect::<Vec<Card>>();
+    let odds = draw_trials(&Deck::from(stones), 2, 50, Threads::Pool).unwrap();
+    assert_eq!(odds[&PokerHand::NONE], 1.0);
+    assert_eq!(odds[&PokerHand::HighCard], 0.0);
+}
+
+#[test]
+fn at_least_odds_add_up_the_higher_hands() {
+    let odds = best_hand_trials(&Deck::new(), 8, 2000, Threads::Pool).unwrap();
+    let total: f64 = odds.best.values().sum();
+    assert!((total - 1.0).abs() < 1e-9);
+    assert!((odds.at_least[&PokerHand::HighCard] - 1.0).abs() < 1e-9);
+    // Each step up the ranking can only lose odds
+    for pair in PokerHand::ALL.windows(2) {
+        assert!(odds.at_least[&pair[1]] <= odds.at_least[&pair[0]] + 1e-9);
+    }
+
+    let best = HashMap::from([(PokerHand::Pair, 0.5), (PokerHand::FullHouse, 0.25)]);
+    let cumulative = at_least(&best);
+    assert_eq!(cumulative[&PokerHand::Pair], 0.75);
+    assert_eq!(cumulative[&PokerHand::Flush], 0.25);
+    assert_eq!(cumulative[&PokerHand::FourOfAKind], 0.0);
+}
+
+#[test]
+fn held_cards_are_always_part_of_the_hand() {
+    let held = Hand::from(suited(Suit::Hearts, &[13, 13]));
+    let mut deck = Deck::new();
+    for card in held.cards.iter() {
+        deck.remove_card(card);
+    }
+    let odds = draw_trials_given(&held, &deck, 3, 500, Threads::Pool).unwrap();
+    assert_eq!(odds[&PokerHand::Pair], 1.0);
+}
+
+#[test]
+fn held_cards_condition_the_odds() {
+    // Four hearts held and only hearts left to draw
+    let held = Hand::from(suited(Suit::Hearts, &[2, 6, 9, 12]));
+    let deck = Deck::from(suited(Suit::Hearts, &[3, 4, 7, 10]));
+    let odds = draw_trials_given(&held, &deck, 1, 200, Threads::Pool).unwrap();
+    assert_eq!(odds[&PokerHand::Flush], 1.0);
+
+    // Drawing nothing just evaluates the held cards
+    let odds = draw_trials_given(&held, &deck, 0, 200, Threads::Pool).unwrap();
+    assert_eq!(odds.len(), PokerHand::ALL.len());
+    assert_eq!(odds[&PokerHand::HighCard], 1.0);
+    assert_eq!(odds[&PokerHand::Flush], 0.0);
+}
+
+#[test]
+fn exact_odds_count_every_draw() {
+    // 22100 hands of three cards, 52 of them three of a kind and 18304 with
+    // three different ranks
+    let odds = exact_odds(&Deck::new(), 3, Threads::Pool).unwrap();
+    assert_eq!(odds[&PokerHand::HighCard], 1.0);
+    assert!((odds[&PokerHand::ThreeOfAKind] - 52.0 / 22100.0).abs() < 1e-12);
+    assert!((odds[&PokerHand::Pair] - (1.0 - 18304.0 / 22100.0)).abs() < 1e-12);
+    assert_eq!(odds[&PokerHand::Flush], 0.0);
+}
+
+#[test]
+fn exact_odds_weight_copies_of_a_card() {
+    // AA, AK and KK are the only different hands, but 4 of the 10 draws pair up
+    let mut cards = suited(Suit::Spades, &[14, 14, 14]);
+    cards.extend(suited(Suit::Hearts, &[13, 13]));
+    let deck = Deck::from(cards);
+    assert_eq!(distinct_draws(&deck, 2), 3);
+    let odds = exact_odds(&deck, 2, Threads::Single).unwrap();
+    assert!((odds[&PokerHand::Pair] - 0.4).abs() < 1e-12);
+}
+
+#[test]
+fn exact_odds_keep_the_held_cards() {
+    let held = Hand::from(vec![
+        Card::new(Rank::Ace, Suit::Spades),
+        Card::new(Rank::Ace, Suit::Hearts),
+    ]);
+    let mut deck = Deck::new();
+    for card in held.cards.iter() {
+        deck.remove_card(card);
+    }
+    let odds = exact_odds_given(&held, &deck, 1, Threads::Pool).unwrap();
+    assert_eq!(odds[&PokerHand::Pair], 1.0);
+    assert!((odds[&PokerHand::ThreeOfAKind] - 2.0 / 50.0).abs() < 1e-12);
+}
+
+#[test]
+fn exact_odds_reject_too_many_draws() {
+    assert!(exact_odds(&Deck::new(), 8, Threads::Pool).is_err());
+    assert!(exact_odds(&Deck::new(), 53, Threads::Pool).is_err());
+}
+
+#[test]
+fn discard_advice_throws_away_the_off_suit_card() {
+    let mut cards = suited(Suit::Hearts, &[2, 5, 9, 13]);
+    cards.push(Card::new(Rank::Three, Suit::Spades));
+    let hand = Hand::from(cards);
+    let mut deck = Deck::new();
+    for card in hand.cards.iter() {
+        deck.remove_card(card);
+    }
+
+    let advice = advise_discards(&hand, &deck, PokerHand::Flush, 2, 1000, Threads::Pool).unwrap();
+    // Nothing, one of the five cards or two of them
+    assert_eq!(advice.len(), 1 + 5 + 10);
+    assert_eq!(
+        advice[0].discard,
+        vec![Card::new(Rank::Three, Suit::Spades)]
+    );
+    assert!(advice[0].exact);
+    assert!((advice[0].odds - 9.0 / 47.0).abs() < 1e-12);
+    // Keeping every card can't make a Flush
+    let keep = advice.iter().find(|a| a.discard.is_empty()).unwrap();
+    assert_eq!(keep.odds, 0.0);
+}
+
+#[test]
+fn round_refills_the_hand_from_the_draw_pile() {
+    let deck = Deck::new();
+    let odds = simulate_round(
+        &deck,
+        8,
+        4,
+        PokerHand::Flush,
+        &PlayPolicy::BestHand,
+        500,
+        Threads::Pool,
+    );
+    assert_eq!(odds.plays.len(), 4);
+    for play in odds.plays.iter() {
+        // With 8 cards in hand there's almost always a pair to play
+        assert!(play.available[&PokerHand::Pair] > 0.5);
+    }
+    // Hitting at least once over the round beats hitting on the first play
+    let first = odds.plays[0]
+        .available
+        .get(&PokerHand::Flush)
+        .unwrap_or(&0.0);
+    assert!(odds.target_hit >= *first);
+}
+
+#[test]
+fn round_stops_when_the_deck_runs_out() {
+    let deck = Deck::from(suited(Suit::Spades, &[2, 4, 6, 8, 10, 12, 14]));
+    let policy = PlayPolicy::Chase {
+        target: PokerHand::Flush,
+    };
+    let odds = simulate_round(&deck, 5, 4, PokerHand::Flush, &policy, 50, Threads::Pool);
+    // The first play is the flush, leaving 2 cards for a second play
+    assert_eq!(odds.plays.len(), 2);
+    assert_eq!(odds.plays[0].played[&PokerHand::Flush], 1.0);
+    assert_eq!(odds.target_hit, 1.0);
+}
+
+#[test]
+fn score_trials_respect_splash() {
+    // Every hand is the same pair of kings with three low kickers
+    let mut cards = suited(Suit::Hearts, &[13, 2, 4]);
+    cards.extend(suited(Suit::Spades, &[13, 6]));
+    let deck = Deck::from(cards);
+
+    let scores = score_trials::<Joker>(
+        &deck,
+        5,
+        &[],
+        &RoundState::default(),
+        20,
+        1,
+        &DEFAULT_PERCENTILES,
+        Threads::Pool,
+    );
+    assert_eq!(scores.by_hand[&PokerHand::Pair].mean, 30.0 * 2.0);
+    let scores = score_trials(
+        &deck,
+        5,
+        &[Joker::Splash],
+        &RoundState::default(),
+        20,
+        1,
+        &DEFAULT_PERCENTILES,
+        Threads::Pool,
+    );
+    let pair = &scores.by_hand[&PokerHand::Pair];
+    assert_eq!(pair.min, (30.0 + 2.0 + 4.0 + 6.0) * 2.0);
+    assert_eq!(pair.max, pair.min);
+    assert_eq!(scores.overall.trials, 20);
+    assert_eq!(scores.glass_destroyed, 0.0);
+}
+
+#[test]
+fn score_trials_sample_glass_and_lucky_cards() {
+    let glass = |rank| Card::new(rank, Suit::Spades).with_enhancement(Enhancement::Glass);
+    let lucky = |rank| Card::new(rank, Suit::Hearts).with_enhancement(Enhancement::Lucky);
+    let deck = Deck::from(vec![
+        glass(Rank::Ace),
+        glass(Rank::King),
+        lucky(Rank::Ace),
+        lucky(Rank::King),
+    ]);
+
+    let trials = 2000;
+    let scores = score_trials::<Joker>(
+        &deck,
+        4,
+        &[],
+        &RoundState::default(),
+        trials,
+        7,
+        &DEFAULT_PERCENTILES,
+        Threads::Pool,
+    );
+    assert_eq!(scores.overall.trials, trials);
+    // Both pairs score, so two glass cards roll to break each hand
+    assert!((scores.glass_destroyed - 0.5).abs() < 0.05);
+    // Lucky cards add mult some of the time
+    assert!(scores.overall.min < scores.overall.max);
+
+    let again = score_trials::<Joker>(
+        &deck,
+        4,
+        &[],
+        &RoundState::default(),
+        trials,
+        7,
+        &DEFAULT_PERCENTILES,
+        Threads::Pool,
+    );
+    assert_eq!(again.overall, scores.overall);
+
+    let doubled = score_trials(
+        &deck,
+        4,
+        &[Joker::OopsAllSixes],
+        &RoundState::default(),
+        trials,
+        7,
+        &[],
+        Threads::Pool,
+    );
+    assert!((doubled.glass_destroyed - 1.0).abs() < 0.05);
+}
+
+#[test]
+fn score_distribution_summarizes_scores() {
+    let scores: Vec<BigNum> = (1..=100)
+        .rev()
+        .map(|score| BigNum::from(score as f64))
+        .collect();
+    let distribution = ScoreDistribution::from_scores(scores, &[0.0, 25.0, 90.0, 100.0]);
+    assert_eq!(distribution.trials, 100);
+    assert_eq!(distribution.min, 1.0);
+    assert_eq!(distribution.max, 100.0);
+    assert_eq!(distribution.mean, 50.5);
+    assert_eq!(distribution.median, 50.5);
+    assert_eq!(distribution.percentile(0.0), Some(BigNum::from(1.0)));
+    assert_eq!(distribution.percentile(25.0), Some(BigNum::from(25.0)));
+    assert_eq!(distribution.percentile(90.0), Some(BigNum::from(90.0)));
+    assert_eq!(distribution.percentile(100.0), Some(BigNum::from(100.0)));
+    assert_eq!(distribution.percentile(50.0), None);
+
+    // Log scale bins, so the low bins are narrow and hold fewer scores
+    let counts: u32 = distribution.histogram.iter().map(|bin| bin.count).sum();
+    assert_eq!(counts, 100);
+    let first = &distribution.histogram[0];
+    let last = &distribution.histogram[distribution.histogram.len() - 1];
+    assert_eq!(first.low, 1.0);
+    assert!((last.high.to_f64() - 100.0).abs() < 1e-9);
+    assert!(first.count < last.count);
+    assert_eq!(
+        distribution.sparkline().chars().count(),
+        distribution.histogram.len()
+    );
+}
+
+#[test]
+fn score_distribution_handles_empty_and_equal_scores() {
+    let empty = ScoreDistribution::from_scores(Vec::new(), &DEFAULT_PERCENTILES);
+    assert_eq!(empty.trials, 0);
+    assert!(empty.histogram.is_empty());
+    assert_eq!(empty.sparkline(), "");
+
+    let equal = ScoreDistribution::from_scores(vec![BigNum::from(60.0); 3], &DEFAULT_PERCENTILES);
+    assert_eq!(equal.median, 60.0);
+    assert_eq!(equal.histogram.len(), 1);
+    assert_eq!(equal.histogram[0].count, 3);
+    assert_eq!(equal.sparkline(), "█");
+}
+
+#[test]
+fn draw_trial_results_have_a_stable_shape() {
+    let deck = Deck::new();
+    let results = draw_trial_results(&deck, 5, 1000, 7, Threads::Pool);
+    assert_eq!(results.version, RESULTS_VERSION);
+    assert_eq!(
+        (results.hand_size, results.trials, results.seed),
+        (5, 1000, 7)
+    );
+    let hands: Vec<PokerHand> = results.results.iter().map(|result| result.hand).collect();
+    assert_eq!(hands, PokerHand::ALL.to_vec());
+    for result in results.results.iter() {
+        assert_eq!(result.probability, result.count as f64 / 1000.0);
+        assert!(result.ci.low <= result.probability && result.probability <= result.ci.high);
+        assert!(result.ci.low >= 0.0 && result.ci.high <= 1.0);
+    }
+    // Even with no hits the interval says how rare the hand could be
+    let flush_five = &results.results[PokerHand::ALL.len() - 1];
+    assert_eq!(flush_five.count, 0);
+    assert!(flush_five.ci.high > 0.0);
+
+    // The same seed draws the same hands, with or without threads
+    let again = draw_trial_results(&deck, 5, 1000, 7, Threads::Single);
+    assert_eq!(
+        results
+            .results
+            .iter()
+            .map(|r| r.count)
+            .collect::<Vec<u32>>(),
+        again.results.iter().map(|r| r.count).collect::<Vec<u32>>()
+    );
+
+    let json = serde_json::to_value(&results).unwrap();
+    for key in [
+        "version",
+        "hand_size",
+        "trials",
+        "seed",
+        "confidence",
+        "elapsed_ms",
+        "results",
+    ] {
+        assert!(json.get(key).is_some(), "missing {}", key);
+    }
+    assert_eq!(json["results"][2]["hand"], "Pair");
+    assert!(json["results"][2]["ci"]["low"].is_number());
+}
+
+#[test]
+fn simulation_steps_add_up_to_a_full_run() {
+    let deck = Deck::new();
+    let mut sim = Simulation::new(deck.clone(), 5, 1000, 11);
+    assert_eq!(sim.step(300, Threads::Pool), 300);
+    let partial = sim.snapshot();
+    assert_eq!((partial.completed, partial.total), (300, 1000));
+    assert_eq!(partial.results.trials, 300);
+    assert!((partial.progress - 0.3).abs() < 1e-9);
+    assert!(!partial.done);
+
+    // Stepping past the end only runs what's left
+    assert_eq!(sim.step(300, Threads::Single), 300);
+    assert_eq!(sim.step(1000, Threads::Pool), 400);
+    assert_eq!(sim.step(1000, Threads::Pool), 0);
+    let full = sim.snapshot();
+    assert!(full.done && !full.cancelled);
+
+    let at_once = draw_trial_results(&deck, 5, 1000, 11, Threads::Pool);
+    let counts = |results: &PokerHandResults| -> Vec<u32> {
+        results.results.iter().map(|result| result.count).collect()
+    };
+    assert_eq!(counts(&full.results), counts(&at_once));
+}
+
+#[test]
+fn cancelled_simulations_keep_their_results() {
+    let mut sim = Simulation::new(Deck::new(), 5, 1000, 3);
+    sim.step(100, Threads::Pool);
+    sim.cancel();
+    assert!(sim.is_done());
+    assert_eq!(sim.step(100, Threads::Pool), 0);
+    let snapshot = sim.snapshot();
+    assert!(snapshot.cancelled && snapshot.done);
+    assert_eq!(snapshot.completed, 100);
+    // Every hand drawn so far contains a High Card
+    let high_card = snapshot
+        .results
+        .results
+        .iter()
+        .find(|result| result.hand == PokerHand::HighCard)
+        .map(|result| result.count);
+    assert_eq!(high_card, Some(100));
+}
@@ -1,18 +1,68 @@
 pub mod statistics {
     use rand::Rng;
 
-    pub fn generate_random_numbers(i: u8, k: u8, n: u8) -> Vec<usize> {
+    // `n` distinct random indices below `len`, at most `len` of them
+    pub fn generate_random_numbers(len: usize, n: usize) -> Vec<usize> {
         let mut rng = rand::thread_rng();
-        let mut numbers: Vec<u8> = (i..=k).collect(); 
-        let mut unique_indices = Vec::with_capacity(n as usize);
+        let mut numbers: Vec<usize> = (0..len).collect();
+        let n = n.min(len);
+        let mut unique_indices = Vec::with_capacity(n);
 
         for _ in 0..n {
-            let range = (k - i + 1) as usize - unique_indices.len(); 
+            let range = len - unique_indices.len(); 
             let index = rng.gen_range(0..range); 
-            unique_indices.push(numbers[index] as usize); 
+            unique_indices.push(numbers[index]); 
             numbers.swap_remove(index); 
         }
 
         unique_indices
     }
+
+    // SplitMix64, used to turn a seed and a value into a well mixed u64
+    // so that the same inputs always give the same random looking key
+    pub fn mix_seed(seed: u64, value: u64) -> u64 {
+        let mut z = seed ^ value.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        z = z.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // Wilson score interval for a proportion, which unlike the normal
+    // approximation stays within 0 and 1 for rare hands
+    pub fn wilson_interval(successes: u32, trials: u32, z: f64) -> (f64, f64) {
+        if trials == 0 {
+            return (0.0, 1.0);
+        }
+        let n = trials as f64;
+        let p = successes as f64 / n;
+        let z2 = z * z;
+        let center = (p + z2 / (2.0 * n)) / (1.0 + z2 / n);
+        let spread = z / (1.0 + z2 / n) * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt();
+        // Rounding can push the ends just past p when it's 0 or 1
+        ((center - spread).clamp(0.0, p), (center + spread).clamp(p, 1.0))
+    }
+}
+
+pub mod clock {
+    // Milliseconds since the epoch, std's clock isn't available in the browser
+    #[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+    pub fn now_ms() -> f64 {
+        js_sys::Date::now()
+    }
+
+    // Without JS there's no clock to read, so nothing is timed
+    #[cfg(all(target_arch = "wasm32", not(feature = "wasm")))]
+    pub fn now_ms() -> f64 {
+        0.0
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn now_ms() -> f64 {
+        use std::time::{SystemTime, UNIX_EPOCH};
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs_f64() * 1000.0)
+            .unwrap_or(0.0)
+    }
 }
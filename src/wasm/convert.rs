@@ -0,0 +1,124 @@
+// Conversions between the core types and the plain JS objects the page uses
+use js_sys::Object;
+use wasm_bindgen::JsValue;
+
+use crate::card::{Card, Enhancement, Rank, Seal, Suit};
+use crate::deck::Deck;
+
+impl Suit {
+    pub(crate) fn from_jsvalue(jsvalue: JsValue) -> Result<Suit, &'static str> {
+        let suit = match jsvalue.as_string() {
+            Some(suit) => suit,
+            None => return Err("Invalid suit object"),
+        };
+        Ok(Suit::from(suit.as_str()))
+    }
+}
+
+impl Rank {
+    pub(crate) fn from_jsvalue(jsvalue: JsValue) -> Result<Rank, &'static str> {
+        let rank = match jsvalue.as_f64() {
+            Some(rank) => rank as usize,
+            None => return Err("Invalid rank object"),
+        };
+        Ok(Rank::from(rank))
+    }
+}
+
+impl Card {
+    pub(crate) fn to_jsvalue(self) -> JsValue {
+        let obj = Object::new();
+        let _ = js_sys::Reflect::set(
+            &obj,
+            &JsValue::from_str("rank"),
+            &JsValue::from_f64(self.rank.to_int() as f64),
+        );
+        let _ = js_sys::Reflect::set(
+            &obj,
+            &JsValue::from_str("suit"),
+            &JsValue::from_str(self.suit.to_str()),
+        );
+        if let Some(seal) = self.seal {
+            let _ = js_sys::Reflect::set(
+                &obj,
+                &JsValue::from_str("seal"),
+                &JsValue::from_str(seal.to_str()),
+            );
+        }
+        if let Some(enhancement) = self.enhancement {
+            let _ = js_sys::Reflect::set(
+                &obj,
+                &JsValue::from_str("enhancement"),
+                &JsValue::from_str(enhancement.to_str()),
+            );
+        }
+        JsValue::from(obj)
+    }
+}
+
+impl Deck {
+    pub(crate) fn to_jsvalue(&self) -> JsValue {
+        let obj = Object::new();
+        let array = js_sys::Array::new();
+        for card in self.cards() {
+            array.push(&card.to_jsvalue());
+        }
+        let _ = js_sys::Reflect::set(&obj, &JsValue::from_str("cards"), &JsValue::from(array));
+
+        JsValue::from(obj)
+    }
+
+    pub(crate) fn from_jsvalue(jsvalue: JsValue) -> Result<Self, String> {
+        // Expect { cards: {{rank: int, suit: str, seal?: str, enhancement?: str}[]} }
+        let obj = js_sys::Object::from(jsvalue);
+        let cards = match js_sys::Reflect::get(&obj, &JsValue::from_str("cards")) {
+            Ok(cards) => cards,
+            Err(_) => return Err("Invalid deck object".to_string()),
+        };
+        if !js_sys::Array::is_array(&cards) {
+            return Err("Invalid deck object".to_string());
+        }
+        let cards_arr = js_sys::Array::from(&cards);
+        let mut cards_vec = Vec::new();
+
+        for i in 0..cards_arr.length() {
+            let suit = match js_sys::Reflect::get(&cards_arr.get(i), &JsValue::from_str("suit")) {
+                Ok(suit) => match suit.as_string() {
+                    Some(suit) => suit,
+                    None => return Err("Invalid card object".to_string()),
+                },
+                Err(_) => return Err("Invalid card object".to_string()),
+            };
+            let rank = match js_sys::Reflect::get(&cards_arr.get(i), &JsValue::from_str("rank")) {
+                Ok(rank) => match rank.as_f64() {
+                    Some(rank) => rank,
+                    None => return Err("Invalid card object".to_string()),
+                },
+                Err(_) => return Err("Invalid card object".to_string()),
+            };
+            // Seals and enhancements are optional
+            let seal = match js_sys::Reflect::get(&cards_arr.get(i), &JsValue::from_str("seal")) {
+                Ok(seal) => match seal.as_string() {
+                    Some(seal) => Some(seal.parse::<Seal>()?),
+                    None => None,
+                },
+                Err(_) => None,
+            };
+            let enhancement =
+                match js_sys::Reflect::get(&cards_arr.get(i), &JsValue::from_str("enhancement")) {
+                    Ok(enhancement) => match enhancement.as_string() {
+                        Some(enhancement) => Some(enhancement.parse::<Enhancement>()?),
+                        None => None,
+                    },
+                    Err(_) => None,
+                };
+            cards_vec.push(Card {
+                rank: Rank::from_int(rank as usize),
+                suit: Suit::from(suit.as_str()),
+                seal,
+                enhancement,
+            });
+        }
+        Ok(Deck::from(cards_vec))
+    }
+}
@@ -0,0 +1,610 @@
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tsify::Tsify;
+use wasm_bindgen::prelude::*; // For initializing statics
+
+use crate::card::{self, Rank, Suit};
+use crate::deck::{self, Deck, DeckOp, DeckPreset};
+use crate::hand::{Hand, PokerHand};
+use crate::joker::Joker;
+use crate::scoring::levels::{HandLevels, LevelUpgrade};
+use crate::scoring::RoundState;
+use crate::simulation::distribution::DEFAULT_PERCENTILES;
+use crate::simulation::progress;
+use crate::simulation::results;
+use crate::simulation::round::{self, PlayPolicy};
+use crate::simulation::search::{self, OpBudget};
+use crate::simulation::{self, Threads};
+
+mod convert;
+
+// static mut DECK: Deck = Deck { cards: Vec::new() };
+static DECK: Lazy<Mutex<Deck>> = Lazy::new(|| Mutex::new(Deck::new()));
+static LEVELS: Lazy<Mutex<HandLevels>> = Lazy::new(|| Mutex::new(HandLevels::default()));
+
+// Set once the web workers are running. Until then trials stay on the calling
+// thread, since using rayon first would claim its global pool for the main
+// thread alone and the workers could no longer start.
+static THREADS_READY: AtomicBool = AtomicBool::new(false);
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = console)]
+    fn error(msg: &str);
+}
+
+// Workers can only share the module's memory through a SharedArrayBuffer,
+// which browsers only allow on cross-origin isolated pages. Some still define
+// SharedArrayBuffer elsewhere but won't send it to a worker, so a page that
+// says it isn't isolated never gets threads.
+#[cfg(all(target_arch = "wasm32", feature = "threads"))]
+fn shared_memory_available() -> bool {
+    let global = |name: &str| {
+        js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str(name))
+            .unwrap_or(JsValue::UNDEFINED)
+    };
+    global("crossOriginIsolated").as_bool() != Some(false)
+        && global("SharedArrayBuffer").is_function()
+}
+
+// Starts `num_threads` web workers for the trials, e.g.
+// `await initThreads(navigator.hardwareConcurrency)`. Without shared memory
+// it resolves straight away and trials keep running on the main thread.
+#[cfg(all(target_arch = "wasm32", feature = "threads"))]
+#[wasm_bindgen(js_name = initThreads)]
+pub fn init_threads(num_threads: usize) -> js_sys::Promise {
+    if !shared_memory_available() {
+        return js_sys::Promise::resolve(&JsValue::UNDEFINED);
+    }
+    let ready = Closure::once(|_: JsValue| THREADS_READY.store(true, Ordering::Release));
+    let started = wasm_bindgen_rayon::init_thread_pool(num_threads).then(&ready);
+    // The closure has to outlive this call to run when the workers are up
+    ready.forget();
+    started
+}
+
+// Whether trials are running on web workers
+#[wasm_bindgen]
+pub fn threads_enabled() -> bool {
+    THREADS_READY.load(Ordering::Acquire)
+}
+
+// Natively rayon always has threads, in the browser only once the workers
+// have started
+fn trial_threads() -> Threads {
+    if cfg!(not(target_arch = "wasm32")) || threads_enabled() {
+        Threads::Pool
+    } else {
+        Threads::Single
+    }
+}
+
+#[wasm_bindgen]
+pub fn parse_deck(deck: JsValue) -> JsValue {
+    let deck = deck::Deck::from_jsvalue(deck);
+    match deck {
+        Ok(deck) => {
+            let mut _deck = DECK.lock().unwrap();
+            *_deck = deck;
+            drop(_deck);
+            JsValue::from_f64(0f64)
+        }
+        Err(e) => {
+            error(&e);
+            JsValue::from_f64(1f64)
+        }
+    }
+}
+
+#[wasm_bindgen]
+pub fn new_deck() -> JsValue {
+    let deck = deck::Deck::new();
+    deck.to_jsvalue()
+}
+
+// Replaces the current deck with one of Balatro's starting decks
+// The seed is only used by the Erratic deck
+#[wasm_bindgen]
+pub fn load_deck_preset(name: String, seed: Option<u64>) -> Result<JsValue, JsValue> {
+    let preset = DeckPreset::from_str(name.as_str(), seed).map_err(JsValue::from_str)?;
+    let deck = Deck::from_preset(preset);
+    let deck_value = deck.to_jsvalue();
+    match DECK.lock() {
+        Ok(mut d) => {
+            *d = deck;
+        }
+        Err(e) => {
+            error(&format!("Failed to lock deck: {}", e));
+            return Err(JsValue::from_str("Failed to lock deck"));
+        }
+    }
+    Ok(deck_value)
+}
+
+#[wasm_bindgen]
+pub fn add_card(suit: String, rank: i32) -> Result<(), JsValue> {
+    match DECK.lock() {
+        Ok(mut d) => {
+            d.add_card(card::Card::new(
+                Rank::from_int(rank as usize),
+                Suit::from(suit.as_str()),
+            ));
+        }
+        Err(e) => {
+            error(&format!("Failed to lock deck: {}", e));
+            return Err(JsValue::from_str("Failed to lock deck"));
+        }
+    }
+    Ok(())
+}
+#[wasm_bindgen]
+pub fn remove_card(suit: JsValue, rank: JsValue) -> Result<(), JsValue> {
+    let suit = card::Suit::from_jsvalue(suit);
+    let rank = card::Rank::from_jsvalue(rank);
+    match (suit, rank) {
+        (Ok(suit), Ok(rank)) => {
+            match DECK.lock() {
+                Ok(mut d) => {
+                    // Removes a sealed or enhanced card of the rank and suit
+                    // when there is no plain one
+                    if !d.remove_card(&card::Card::new(rank, suit)) {
+                        return Err(JsValue::from_str("Card is not in the deck"));
+                    }
+                }
+                Err(e) => {
+                    error(&format!("Failed to lock deck: {}", e));
+                    return Err(JsValue::from_str("Failed to lock deck"));
+                }
+            }
+            Ok(())
+        }
+        _ => Err(JsValue::from_str("Invalid suit or rank")),
+    }
+}
+
+// Applies a tarot or spectral card to the current deck and returns the new deck
+// The op is an object like { op: "Strength", cards: [0, 1] }
+#[wasm_bindgen]
+pub fn apply_deck_op(op: JsValue) -> Result<JsValue, JsValue> {
+    let op: DeckOp = serde_wasm_bindgen::from_value(op)
+        .map_err(|e| JsValue::from_str(&format!("Invalid deck op: {}", e)))?;
+    match DECK.lock() {
+        Ok(mut d) => {
+            d.apply(&op).map_err(|e| JsValue::from_str(&e))?;
+            Ok(d.to_jsvalue())
+        }
+        Err(e) => {
+            error(&format!("Failed to lock deck: {}", e));
+            Err(JsValue::from_str("Failed to lock deck"))
+        }
+    }
+}
+
+// Returns what the deck would look like after the op, without changing it
+#[wasm_bindgen]
+pub fn preview_deck_op(op: JsValue) -> Result<JsValue, JsValue> {
+    let op: DeckOp = serde_wasm_bindgen::from_value(op)
+        .map_err(|e| JsValue::from_str(&format!("Invalid deck op: {}", e)))?;
+    let mut deck = match DECK.lock() {
+        Ok(d) => d.clone(),
+        Err(e) => {
+            error(&format!("Failed to lock deck: {}", e));
+            return Err(JsValue::from_str("Failed to lock deck"));
+        }
+    };
+    deck.apply(&op).map_err(|e| JsValue::from_str(&e))?;
+    Ok(deck.to_jsvalue())
+}
+
+// Reverts the last change to the deck, an op or an added or removed card, and
+// returns the deck
+#[wasm_bindgen]
+pub fn undo_deck_op() -> Result<JsValue, JsValue> {
+    match DECK.lock() {
+        Ok(mut d) => {
+            if !d.undo() {
+                return Err(JsValue::from_str("Nothing to undo"));
+            }
+            Ok(d.to_jsvalue())
+        }
+        Err(e) => {
+            error(&format!("Failed to lock deck: {}", e));
+            Err(JsValue::from_str("Failed to lock deck"))
+        }
+    }
+}
+
+// Searches for the deck modifications within the budget that give the best
+// odds of the target hand, e.g. { kind: "Destroy", count: 2 }
+#[wasm_bindgen]
+pub fn search_deck_improvements(
+    budget: JsValue,
+    target: PokerHand,
+    hand_size: u8,
+    trials: u32,
+    top: usize,
+    seed: Option<u64>,
+) -> Result<JsValue, JsValue> {
+    let budget: OpBudget = serde_wasm_bindgen::from_value(budget)
+        .map_err(|e| JsValue::from_str(&format!("Invalid budget: {}", e)))?;
+    let deck = match DECK.lock() {
+        Ok(d) => d.clone(),
+        Err(e) => {
+            error(&format!("Failed to lock deck: {}", e));
+            return Err(JsValue::from_str("Failed to lock deck"));
+        }
+    };
+    let seed = seed.unwrap_or_else(rand::random);
+    let results = search::search_improvements(
+        &deck,
+        &budget,
+        target,
+        hand_size,
+        trials,
+        seed,
+        top,
+        trial_threads(),
+    );
+    serde_wasm_bindgen::to_value(&results).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+#[wasm_bindgen]
+pub fn show_deck() -> JsValue {
+    match DECK.lock() {
+        Ok(d) => d.to_jsvalue(),
+        Err(e) => {
+            error(&format!("Failed to lock deck: {}", e));
+            JsValue::NULL
+        }
+    }
+}
+
+// The TypeScript declarations of these come from the Rust types with tsify
+type PokerHandResultsJs = <results::PokerHandResults as Tsify>::JsType;
+type SimulationSnapshotJs = <progress::SimulationSnapshot as Tsify>::JsType;
+
+// Seeds can be bigger than a JS number can hold exactly, so u64s become BigInts
+fn to_js_with_bigints<T: Serialize, J: JsCast>(value: &T) -> Result<J, JsValue> {
+    let serializer =
+        serde_wasm_bindgen::Serializer::new().serialize_large_number_types_as_bigints(true);
+    value
+        .serialize(&serializer)
+        .map(JsCast::unchecked_into)
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+// Maps become plain objects rather than JS Maps, which JSON.stringify would
+// turn into {}, so the page can save them
+fn to_js_object<T: Serialize>(value: &T) -> Result<JsValue, JsValue> {
+    let serializer = serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true);
+    value
+        .serialize(&serializer)
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+// Odds of every poker hand, see `PokerHandResults` for the shape
+// Every hand is listed from the lowest to the highest ranked, with its count,
+// probability and 95% confidence interval
+#[wasm_bindgen]
+pub fn draw_trial(
+    hand_size: u8,
+    trials: u32,
+    seed: Option<u64>,
+) -> Result<PokerHandResultsJs, JsValue> {
+    let deck = match DECK.lock() {
+        Ok(d) => d.clone(),
+        Err(e) => {
+            error(&format!("Failed to lock deck: {}", e));
+            return Err(JsValue::from_str("Failed to lock deck"));
+        }
+    };
+    let seed = seed.unwrap_or_else(rand::random);
+    let results = results::draw_trial_results(&deck, hand_size, trials, seed, trial_threads());
+    to_js_with_bigints(&results)
+}
+
+/// Odds worked out a batch at a time on the current deck, so the page stays
+/// responsive and can show partial results, e.g.
+/// `while (!sim.done) { sim.step(5000); show(sim.snapshot()); await nextFrame(); }`
+#[wasm_bindgen]
+pub struct Simulation {
+    inner: progress::Simulation,
+}
+
+#[wasm_bindgen]
+impl Simulation {
+    #[wasm_bindgen(constructor)]
+    pub fn new(hand_size: u8, trials: u32, seed: Option<u64>) -> Result<Simulation, JsValue> {
+        let deck = match DECK.lock() {
+            Ok(d) => d.clone(),
+            Err(e) => {
+                error(&format!("Failed to lock deck: {}", e));
+                return Err(JsValue::from_str("Failed to lock deck"));
+            }
+        };
+        let seed = seed.unwrap_or_else(rand::random);
+        Ok(Simulation {
+            inner: progress::Simulation::new(deck, hand_size, trials, seed),
+        })
+    }
+
+    // Runs up to `n` more trials and returns how many ran
+    pub fn step(&mut self, n: u32) -> u32 {
+        self.inner.step(n, trial_threads())
+    }
+
+    // The results so far, see `SimulationSnapshot` for the shape
+    pub fn snapshot(&self) -> Result<SimulationSnapshotJs, JsValue> {
+        to_js_with_bigints(&self.inner.snapshot())
+    }
+
+    // Stops any further steps, the results so far are kept
+    pub fn cancel(&mut self) {
+        self.inner.cancel();
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn done(&self) -> bool {
+        self.inner.is_done()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn progress(&self) -> f64 {
+        self.inner.progress()
+    }
+}
+
+// Odds of the best hand that can be made from a drawn hand
+// Returns { best, at_least } maps of PokerHand to probability, or throws when
+// the deck is empty or smaller than the hand
+#[wasm_bindgen]
+pub fn draw_trial_best(hand_size: u8, trials: u32) -> Result<JsValue, JsValue> {
+    let deck = match DECK.lock() {
+        Ok(d) => d.clone(),
+        Err(e) => {
+            error(&format!("Failed to lock deck: {}", e));
+            return Err(JsValue::from_str("Failed to lock deck"));
+        }
+    };
+    let odds = simulation::best_hand_trials(&deck, hand_size, trials, trial_threads())
+        .map_err(|e| JsValue::from_str(&e))?;
+    serde_wasm_bindgen::to_value(&odds).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+// Odds when the given cards are already held and `to_draw` more are drawn
+// The held cards use the same shape as a deck, { cards: [{ rank, suit }] },
+// and are taken out of the current deck before drawing, throws when fewer than
+// `to_draw` cards are left
+#[wasm_bindgen]
+pub fn draw_trial_given(held: JsValue, to_draw: u8, trials: u32) -> Result<JsValue, JsValue> {
+    let held = Deck::from_jsvalue(held).map_err(|e| JsValue::from_str(&e))?;
+    let mut deck = match DECK.lock() {
+        Ok(d) => d.clone(),
+        Err(e) => {
+            error(&format!("Failed to lock deck: {}", e));
+            return Err(JsValue::from_str("Failed to lock deck"));
+        }
+    };
+    for card in held.cards().iter() {
+        if !deck.remove_card(card) {
+            return Err(JsValue::from_str(&format!(
+                "Held card {} is not in the deck",
+                card.to_str()
+            )));
+        }
+    }
+    let held = Hand::from(held.into_cards());
+    let net_result = simulation::draw_trials_given(&held, &deck, to_draw, trials, trial_threads())
+        .map_err(|e| JsValue::from_str(&e))?;
+    serde_wasm_bindgen::to_value(&net_result).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+// Simulates a round of `hands_left` plays where played cards are gone and the
+// hand is refilled from the same draw pile. The policy is "best" to always
+// play the best hand, or "chase" to dig for the target hand.
+#[wasm_bindgen]
+pub fn simulate_round(
+    hand_size: u8,
+    hands_left: u8,
+    target: PokerHand,
+    policy: String,
+    trials: u32,
+) -> Result<JsValue, JsValue> {
+    let policy = PlayPolicy::from_str(policy.as_str(), target).map_err(JsValue::from_str)?;
+    let deck = match DECK.lock() {
+        Ok(d) => d.clone(),
+        Err(e) => {
+            error(&format!("Failed to lock deck: {}", e));
+            return Err(JsValue::from_str("Failed to lock deck"));
+        }
+    };
+    let odds = round::simulate_round(
+        &deck,
+        hand_size,
+        hands_left,
+        target,
+        &policy,
+        trials,
+        trial_threads(),
+    );
+    serde_wasm_bindgen::to_value(&odds).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+// Scores hands played from the current deck with the jokers, given as an
+// array of names in lineup order, e.g. ["Jolly", "Splash"]
+// Returns { overall, by_hand, glass_destroyed } where each distribution is
+// { trials, min, max, mean, median, percentiles, histogram }
+// Scores are strings like "1234", or "1.5*2^1400" past the range of a JS
+// number, so big scores keep their precision. `final_hand` scores the hands as
+// the last one of the round, which jokers like Dusk look at.
+#[wasm_bindgen]
+pub fn score_trial(
+    hand_size: u8,
+    trials: u32,
+    jokers: JsValue,
+    seed: Option<u64>,
+    percentiles: Option<Vec<f64>>,
+    final_hand: Option<bool>,
+) -> Result<JsValue, JsValue> {
+    let jokers: Vec<Joker> = serde_wasm_bindgen::from_value(jokers)
+        .map_err(|e| JsValue::from_str(&format!("Invalid jokers: {}", e)))?;
+    let deck = match DECK.lock() {
+        Ok(d) => d.clone(),
+        Err(e) => {
+            error(&format!("Failed to lock deck: {}", e));
+            return Err(JsValue::from_str("Failed to lock deck"));
+        }
+    };
+    let levels = match LEVELS.lock() {
+        Ok(l) => l.clone(),
+        Err(e) => {
+            error(&format!("Failed to lock hand levels: {}", e));
+            return Err(JsValue::from_str("Failed to lock hand levels"));
+        }
+    };
+    let round = RoundState {
+        final_hand: final_hand.unwrap_or(false),
+        levels,
+    };
+    let seed = seed.unwrap_or_else(rand::random);
+    let percentiles = percentiles.unwrap_or_else(|| DEFAULT_PERCENTILES.to_vec());
+    let scores = simulation::score_trials(
+        &deck,
+        hand_size,
+        &jokers,
+        &round,
+        trials,
+        seed,
+        &percentiles,
+        trial_threads(),
+    );
+    serde_wasm_bindgen::to_value(&scores).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+// The level, chips and mult of every poker hand used for scoring, as an
+// object keyed by poker hand that can be saved with JSON.stringify
+#[wasm_bindgen]
+pub fn show_hand_levels() -> Result<JsValue, JsValue> {
+    let levels = match LEVELS.lock() {
+        Ok(l) => l.clone(),
+        Err(e) => {
+            error(&format!("Failed to lock hand levels: {}", e));
+            return Err(JsValue::from_str("Failed to lock hand levels"));
+        }
+    };
+    to_js_object(&levels)
+}
+
+// Restores hand levels saved from `show_hand_levels`, or resets them to
+// level 1 when given null
+#[wasm_bindgen]
+pub fn load_hand_levels(levels: JsValue) -> Result<(), JsValue> {
+    let levels: HandLevels = if levels.is_null() || levels.is_undefined() {
+        HandLevels::default()
+    } else {
+        serde_wasm_bindgen::from_value(levels)
+            .map_err(|e| JsValue::from_str(&format!("Invalid hand levels: {}", e)))?
+    };
+    match LEVELS.lock() {
+        Ok(mut l) => {
+            *l = levels;
+        }
+        Err(e) => {
+            error(&format!("Failed to lock hand levels: {}", e));
+            return Err(JsValue::from_str("Failed to lock hand levels"));
+        }
+    }
+    Ok(())
+}
+
+// Applies a planet card, Black Hole or The Burnt Joker, e.g.
+// { kind: "Planet", planet: "Mercury" } or { kind: "BlackHole" }
+// Returns the new hand levels
+#[wasm_bindgen]
+pub fn upgrade_hand_levels(upgrade: JsValue) -> Result<JsValue, JsValue> {
+    let upgrade: LevelUpgrade = serde_wasm_bindgen::from_value(upgrade)
+        .map_err(|e| JsValue::from_str(&format!("Invalid upgrade: {}", e)))?;
+    let levels = match LEVELS.lock() {
+        Ok(mut l) => {
+            l.apply(&upgrade);
+            l.clone()
+        }
+        Err(e) => {
+            error(&format!("Failed to lock hand levels: {}", e));
+            return Err(JsValue::from_str("Failed to lock hand levels"));
+        }
+    };
+    to_js_object(&levels)
+}
+
+// Odds for every hand size from min to max, computed from the same draws
+// Returns an array of { hand_size, odds } ordered by hand size, or throws when
+// the range is empty or the largest hand doesn't fit in the deck
+#[wasm_bindgen]
+pub fn draw_trial_sweep(
+    min_hand_size: u8,
+    max_hand_size: u8,
+    trials: u32,
+) -> Result<JsValue, JsValue> {
+    let deck = match DECK.lock() {
+        Ok(d) => d.clone(),
+        Err(e) => {
+            error(&format!("Failed to lock deck: {}", e));
+            return Err(JsValue::from_str("Failed to lock deck"));
+        }
+    };
+    let sweep =
+        simulation::sweep_hand_sizes(&deck, min_hand_size, max_hand_size, trials, trial_threads())
+            .map_err(|e| JsValue::from_str(&e))?;
+    serde_wasm_bindgen::to_value(&sweep).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+// Compares the odds of the current deck against another deck object
+// Returns { base, other, delta } maps of PokerHand to probability
+#[wasm_bindgen]
+pub fn compare_deck(
+    other: JsValue,
+    hand_size: u8,
+    trials: u32,
+    seed: Option<u64>,
+) -> Result<JsValue, JsValue> {
+    let other = Deck::from_jsvalue(other).map_err(|e| JsValue::from_str(&e))?;
+    let deck = match DECK.lock() {
+        Ok(d) => d.clone(),
+        Err(e) => {
+            error(&format!("Failed to lock deck: {}", e));
+            return Err(JsValue::from_str("Failed to lock deck"));
+        }
+    };
+    let seed = seed.unwrap_or_else(rand::random);
+    let comparison =
+        simulation::compare_decks(&deck, &other, hand_size, trials, seed, trial_threads());
+    serde_wasm_bindgen::to_value(&comparison).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+// Compares the odds of the current deck against the deck after applying an op
+#[wasm_bindgen]
+pub fn compare_deck_op(
+    op: JsValue,
+    hand_size: u8,
+    trials: u32,
+    seed: Option<u64>,
+) -> Result<JsValue, JsValue> {
+    let op: DeckOp = serde_wasm_bindgen::from_value(op)
+        .map_err(|e| JsValue::from_str(&format!("Invalid deck op: {}", e)))?;
+    let deck = match DECK.lock() {
+        Ok(d) => d.clone(),
+        Err(e) => {
+            error(&format!("Failed to lock deck: {}", e));
+            return Err(JsValue::from_str("Failed to lock deck"));
+        }
+    };
+    let mut other = deck.clone();
+    other.apply(&op).map_err(|e| JsValue::from_str(&e))?;
+    let seed = seed.unwrap_or_else(rand::random);
+    let comparison =
+        simulation::compare_decks(&deck, &other, hand_size, trials, seed, trial_threads());
+    serde_wasm_bindgen::to_value(&comparison).map_err(|e| JsValue::from_str(&e.to_string()))
+}
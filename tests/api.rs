@@ -0,0 +1,57 @@
+use balatro_handy_odds_calculator::{
+    draw_trial_results, score_trials, Card, Deck, DeckPreset, Hand, Joker, Play, PokerHand, Rank,
+    RoundState, Simulation, Suit, Threads, DEFAULT_PERCENTILES,
+};
+
+#[test]
+fn evaluates_a_hand_from_outside_the_crate() {
+    let cards = vec![
+        Card::new(Rank::Ace, Suit::Spades),
+        Card::new(Rank::Ace, Suit::Hearts),
+        Card::new(Rank::Ace, Suit::Clubs),
+        Card::new(Rank::King, Suit::Spades),
+        Card::new(Rank::King, Suit::Hearts),
+    ];
+    assert_eq!(Hand::from(cards.clone()).best_hand(), PokerHand::FullHouse);
+    let play = Play::new(cards);
+    assert_eq!(play.hand, PokerHand::FullHouse);
+    assert_eq!(play.forming.len(), 5);
+}
+
+#[test]
+fn runs_the_trials_from_outside_the_crate() {
+    let deck = Deck::from_preset(DeckPreset::from_str("abandoned", None).unwrap());
+    let results = draw_trial_results(&deck, 8, 500, 3, Threads::Pool);
+    assert_eq!(results.trials, 500);
+    assert_eq!(results.results.len(), PokerHand::ALL.len());
+
+    let mut simulation = Simulation::new(deck, 8, 500, 3);
+    while simulation.step(200, Threads::Single) > 0 {}
+    assert_eq!(simulation.snapshot().results.results, results.results);
+}
+
+#[test]
+fn parses_jokers_by_name() {
+    assert_eq!("Four Fingers".parse::<Joker>(), Ok(Joker::FourFingers));
+    assert!("Not a joker".parse::<Joker>().is_err());
+}
+
+#[test]
+fn scores_hands_from_outside_the_crate() {
+    let mut deck = Deck::new();
+    assert!(deck.remove_card(&Card::new(Rank::Ace, Suit::Spades)));
+    assert_eq!(deck.cards().len(), 51);
+    assert!(deck.undo());
+
+    let scores = score_trials(
+        &deck,
+        8,
+        &[Joker::Joker],
+        &RoundState::default(),
+        200,
+        5,
+        &DEFAULT_PERCENTILES,
+        Threads::Single,
+    );
+    assert_eq!(scores.overall.trials, 200);
+}
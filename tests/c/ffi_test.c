@@ -0,0 +1,132 @@
+// Exercises the C ABI the way a mod would, build and run it from the repo root:
+// cargo build --release --no-default-features --features ffi
+// cc tests/c/ffi_test.c -Iinclude -Ltarget/release -lbalatro_handy_odds_calculator -lm -o target/ffi_test
+// LD_LIBRARY_PATH=target/release target/ffi_test
+#include <assert.h>
+#include <math.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "balatro_odds.h"
+
+static BalatroCard card(uint8_t rank, uint8_t suit) {
+    BalatroCard card = {rank, suit, BALATRO_SEAL_NONE, BALATRO_ENHANCEMENT_NONE};
+    return card;
+}
+
+static int hand_index(const char *name) {
+    for (int i = 0; i < BALATRO_POKER_HAND_COUNT; i++) {
+        if (strcmp(balatro_poker_hand_name(i), name) == 0) {
+            return i;
+        }
+    }
+    return -1;
+}
+
+static void test_evaluates_hands(void) {
+    BalatroCard cards[] = {
+        card(13, BALATRO_SUIT_SPADES), card(13, BALATRO_SUIT_HEARTS), card(13, BALATRO_SUIT_CLUBS),
+        card(9, BALATRO_SUIT_SPADES),  card(9, BALATRO_SUIT_HEARTS),
+    };
+    int best = balatro_best_hand(cards, 5);
+    assert(strcmp(balatro_poker_hand_name(best), "FullHouse") == 0);
+
+    uint32_t counts[BALATRO_POKER_HAND_COUNT];
+    assert(balatro_evaluate_poker_hands(cards, 5, counts));
+    assert(counts[hand_index("FullHouse")] == 1);
+    assert(counts[hand_index("Flush")] == 0);
+
+    // A Stone card has no rank, so the kings only make a high card
+    BalatroCard stone_pair[] = {card(13, BALATRO_SUIT_HEARTS), card(13, BALATRO_SUIT_SPADES)};
+    stone_pair[0].enhancement = BALATRO_ENHANCEMENT_STONE;
+    assert(balatro_evaluate_poker_hands(stone_pair, 2, counts));
+    assert(counts[hand_index("HighCard")] == 1);
+    assert(counts[hand_index("Pair")] == 0);
+    assert(balatro_best_hand(stone_pair, 2) == hand_index("HighCard"));
+
+    BalatroCard invalid = card(15, BALATRO_SUIT_SPADES);
+    assert(balatro_best_hand(&invalid, 1) == -1);
+    assert(balatro_poker_hand_name(BALATRO_POKER_HAND_COUNT) == NULL);
+}
+
+static void test_builds_decks(void) {
+    BalatroDeck *deck = balatro_deck_new();
+    assert(balatro_deck_size(deck) == 52);
+
+    BalatroCard stone = card(14, BALATRO_SUIT_SPADES);
+    stone.enhancement = BALATRO_ENHANCEMENT_STONE;
+    assert(balatro_deck_add_card(deck, stone));
+    BalatroCard last;
+    assert(balatro_deck_card(deck, 52, &last));
+    assert(last.rank == 14 && last.enhancement == BALATRO_ENHANCEMENT_STONE);
+    assert(!balatro_deck_card(deck, 53, &last));
+    assert(balatro_deck_remove_card(deck, stone));
+    assert(!balatro_deck_remove_card(deck, stone));
+    assert(balatro_deck_size(deck) == 52);
+    balatro_deck_free(deck);
+
+    BalatroDeck *abandoned = balatro_deck_from_preset("Abandoned", 0);
+    assert(balatro_deck_size(abandoned) == 40);
+    balatro_deck_free(abandoned);
+    assert(balatro_deck_from_preset("Not a deck", 0) == NULL);
+}
+
+static void test_odds(void) {
+    BalatroDeck *deck = balatro_deck_new();
+
+    BalatroOdds odds[BALATRO_POKER_HAND_COUNT], again[BALATRO_POKER_HAND_COUNT];
+    assert(balatro_draw_trial(deck, 8, 2000, 42, odds));
+    assert(balatro_draw_trial(deck, 8, 2000, 42, again));
+    BalatroOdds pair = odds[hand_index("Pair")];
+    assert(pair.count == again[hand_index("Pair")].count);
+    assert(pair.probability > 0.8 && pair.probability < 1.0);
+    assert(pair.ci_low <= pair.probability && pair.probability <= pair.ci_high);
+
+    double best[BALATRO_POKER_HAND_COUNT], at_least[BALATRO_POKER_HAND_COUNT];
+    assert(balatro_draw_trial_best(deck, 8, 2000, best, at_least));
+    double total = 0.0;
+    for (int i = 0; i < BALATRO_POKER_HAND_COUNT; i++) {
+        total += best[i];
+    }
+    assert(fabs(total - 1.0) < 1e-9);
+    assert(at_least[hand_index("HighCard")] > 0.999);
+
+    BalatroCard held[] = {card(14, BALATRO_SUIT_SPADES), card(14, BALATRO_SUIT_HEARTS)};
+    double given[BALATRO_POKER_HAND_COUNT];
+    assert(balatro_draw_trial_given(deck, held, 2, 3, 500, given));
+    assert(given[hand_index("Pair")] == 1.0);
+
+    assert(!balatro_draw_trial(NULL, 8, 10, 0, odds));
+    balatro_deck_free(deck);
+}
+
+static void test_rejects_draws_the_deck_cannot_cover(void) {
+    BalatroCard pair[] = {card(14, BALATRO_SUIT_SPADES), card(14, BALATRO_SUIT_HEARTS)};
+    BalatroDeck *small = balatro_deck_from_cards(pair, 2);
+    BalatroOdds odds[BALATRO_POKER_HAND_COUNT];
+    double best[BALATRO_POKER_HAND_COUNT], given[BALATRO_POKER_HAND_COUNT];
+    assert(!balatro_draw_trial(small, 3, 10, 0, odds));
+    assert(!balatro_draw_trial_best(small, 3, 10, best, NULL));
+    assert(!balatro_draw_trial_given(small, pair, 1, 2, 10, given));
+    assert(balatro_draw_trial_given(small, pair, 2, 0, 10, given));
+    assert(given[hand_index("Pair")] == 1.0);
+    balatro_deck_free(small);
+
+    BalatroDeck *empty = balatro_deck_from_cards(NULL, 0);
+    assert(balatro_deck_size(empty) == 0);
+    assert(!balatro_draw_trial(empty, 0, 10, 0, odds));
+    assert(!balatro_draw_trial_best(empty, 0, 10, best, NULL));
+    assert(!balatro_draw_trial_best(empty, 5, 10, best, NULL));
+    assert(!balatro_draw_trial_given(empty, NULL, 0, 1, 10, given));
+    balatro_deck_free(empty);
+}
+
+int main(void) {
+    assert(balatro_abi_version() == BALATRO_ABI_VERSION);
+    test_evaluates_hands();
+    test_builds_decks();
+    test_odds();
+    test_rejects_draws_the_deck_cannot_cover();
+    printf("ffi tests passed\n");
+    return 0;
+}
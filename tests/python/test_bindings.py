@@ -0,0 +1,97 @@
+# Checks the balatro_odds module built with `maturin develop`, run from the
+# repo root with `python -m unittest discover tests/python`
+import unittest
+
+import balatro_odds as bo
+
+
+class BindingsTest(unittest.TestCase):
+    def test_evaluates_a_hand(self):
+        hand = bo.Hand(
+            [
+                bo.Card(14, "Spades"),
+                bo.Card(14, "Hearts"),
+                bo.Card(13, "Spades"),
+                bo.Card(13, "Clubs"),
+                bo.Card(13, "Hearts"),
+            ]
+        )
+        self.assertEqual(hand.best_hand(), "FullHouse")
+        self.assertEqual(hand.evaluate_poker_hands()["FullHouse"], 1)
+
+    def test_evaluates_the_same_hands_as_best_hand(self):
+        stone = bo.Card(13, "Hearts", enhancement="Stone")
+        hand = bo.Hand([stone, bo.Card(13, "Spades")])
+        self.assertEqual(hand.best_hand(), "HighCard")
+        self.assertEqual(hand.evaluate_poker_hands(), {"HighCard": 1})
+        self.assertEqual(bo.Hand([stone]).evaluate_poker_hands(), {"None": 1})
+
+    def test_trial_results_are_dicts_for_every_hand(self):
+        results = bo.Deck().draw_trial(8, 1000, seed=3)
+        self.assertEqual(results["trials"], 1000)
+        self.assertEqual([row["hand"] for row in results["results"]], bo.POKER_HANDS)
+        self.assertEqual(bo.Deck().draw_trial(8, 1000, seed=3)["results"], results["results"])
+
+    def test_held_cards_condition_the_odds(self):
+        held = bo.Hand([bo.Card(14, "Spades"), bo.Card(14, "Hearts")])
+        self.assertEqual(bo.Deck().draw_trial_given(held, 3, 200)["Pair"], 1.0)
+
+    def test_exact_odds_count_every_hand(self):
+        odds = bo.Deck().exact_odds(3)
+        self.assertEqual(set(odds), set(bo.POKER_HANDS))
+        self.assertAlmostEqual(odds["ThreeOfAKind"], 52 / 22100)
+        held = bo.Hand([bo.Card(14, "Spades"), bo.Card(14, "Hearts")])
+        self.assertAlmostEqual(bo.Deck().exact_odds_given(held, 1)["ThreeOfAKind"], 2 / 50)
+        with self.assertRaises(ValueError):
+            bo.Deck().exact_odds(8)
+
+    def test_discard_advice_ranks_the_best_discard_first(self):
+        hearts = [bo.Card(rank, "Hearts") for rank in (2, 5, 9, 13)]
+        hand = bo.Hand(hearts + [bo.Card(3, "Spades")])
+        advice = bo.Deck().advise_discards(hand, "Flush", max_discards=1)
+        self.assertEqual(advice[0]["discard"], [bo.Card(3, "Spades")])
+        self.assertAlmostEqual(advice[0]["odds"], 9 / 47)
+        self.assertTrue(advice[0]["exact"])
+        self.assertEqual(len(advice), 6)
+        with self.assertRaises(ValueError):
+            bo.Deck().advise_discards(hand, "Flushes")
+
+    def test_plain_cards_remove_modified_cards(self):
+        deck = bo.Deck()
+        deck.add_card(bo.Card(14, "Spades", enhancement="Glass"))
+        deck.remove_card(bo.Card(14, "Spades"))
+        deck.remove_card(bo.Card(14, "Spades"))
+        self.assertEqual(len(deck), 51)
+        with self.assertRaises(ValueError):
+            deck.remove_card(bo.Card(14, "Spades"))
+
+    def test_rejects_draws_larger_than_the_deck(self):
+        deck = bo.Deck()
+        with self.assertRaises(ValueError):
+            deck.draw_hand(53)
+        with self.assertRaises(ValueError):
+            deck.draw_trial_best(53, 10)
+        with self.assertRaises(ValueError):
+            deck.draw_trial(53, 10)
+        held = bo.Hand([bo.Card(14, "Spades")])
+        with self.assertRaises(ValueError):
+            deck.draw_trial_given(held, 52, 10)
+
+    def test_rejects_draws_from_an_empty_deck(self):
+        deck = bo.Deck()
+        for card in deck.cards:
+            deck.remove_card(card)
+        with self.assertRaises(ValueError):
+            deck.draw_hand(0)
+        with self.assertRaises(ValueError):
+            deck.draw_trial_best(5, 10)
+
+    def test_rejects_invalid_cards(self):
+        with self.assertRaises(ValueError):
+            bo.Card(15, "Spades")
+        with self.assertRaises(ValueError):
+            bo.Card(2, "Spades", seal="Green")
+
+
+if __name__ == "__main__":
+    unittest.main()
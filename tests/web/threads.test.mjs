@@ -0,0 +1,90 @@
+// Loads the threaded package in headless Chrome, once from a cross-origin
+// isolated server where the web workers start and once from a server without
+// the isolation headers where trials stay on the main thread, and checks both
+// give the same odds and scores. Also checks hand levels survive a round trip
+// through JSON.
+// Build the package first from the repo root, see "Threads in the browser" in
+// the README for the command
+import assert from "node:assert/strict";
+import { readFile } from "node:fs/promises";
+import { createServer } from "node:http";
+import { extname, join, normalize } from "node:path";
+import { fileURLToPath } from "node:url";
+import puppeteer from "puppeteer";
+
+const root = fileURLToPath(new URL("../..", import.meta.url));
+const types = {
+  ".html": "text/html",
+  ".js": "text/javascript",
+  ".wasm": "application/wasm",
+};
+
+// Workers only get shared memory on cross-origin isolated pages, so the same
+// files are served with and without the headers that isolate the page
+function serve(isolated) {
+  const headers = isolated
+    ? {
+        "Cross-Origin-Opener-Policy": "same-origin",
+        "Cross-Origin-Embedder-Policy": "require-corp",
+      }
+    : {};
+  return createServer(async (req, res) => {
+    const path = normalize(decodeURIComponent(new URL(req.url, "http://localhost").pathname));
+    try {
+      const body = await readFile(join(root, path));
+      res.writeHead(200, {
+        "Content-Type": types[extname(path)] ?? "application/octet-stream",
+        ...headers,
+      });
+      res.end(body);
+    } catch {
+      res.writeHead(404);
+      res.end();
+    }
+  });
+}
+
+async function listen(server) {
+  await new Promise((resolve) => server.listen(0, resolve));
+  return `http://localhost:${server.address().port}/tests/web/index.html`;
+}
+
+const isolatedServer = serve(true);
+const plainServer = serve(false);
+const isolatedUrl = await listen(isolatedServer);
+const plainUrl = await listen(plainServer);
+
+const browser = await puppeteer.launch({ headless: true });
+async function run(url) {
+  const page = await browser.newPage();
+  page.on("console", (message) => console.log(`[page] ${message.text()}`));
+  await page.goto(url);
+  await page.waitForFunction(() => window.testResult !== undefined, { timeout: 120000 });
+  const result = await page.evaluate(() => window.testResult);
+  await page.close();
+  assert.equal(result.error, undefined, result.error);
+  return result;
+}
+
+try {
+  const threaded = await run(isolatedUrl);
+  assert.equal(threaded.isolated, true);
+  assert.equal(threaded.threads, true, "the workers should start");
+  assert.equal(threaded.version, 1);
+  assert.equal(threaded.savedLevels.levels.Flush.level, 2, "levels should save as an object");
+  assert.deepEqual(threaded.loadedLevels, threaded.savedLevels);
+
+  const fallback = await run(plainUrl);
+  assert.equal(fallback.isolated, false);
+  assert.equal(fallback.threads, false, "no shared memory should fall back");
+  assert.equal(fallback.version, 1);
+  assert.ok(fallback.counts.length > 0, "the fallback should return results");
+  // Seeded trials give the same counts however they're run
+  assert.deepEqual(fallback.counts, threaded.counts);
+  assert.deepEqual(fallback.scores, threaded.scores);
+  console.log("threaded and single-threaded trials agree");
+} finally {
+  await browser.close();
+  isolatedServer.close();
+  plainServer.close();
+}